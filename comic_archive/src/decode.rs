@@ -0,0 +1,355 @@
+//! Decode page bytes into a `DynamicImage`, dispatching by file extension to
+//! the codecs that the stock `image` crate doesn't cover on its own.
+
+use crate::error::ArchiveError;
+use image::DynamicImage;
+
+#[cfg(feature = "svg")]
+fn decode_svg(buf: &[u8]) -> Option<DynamicImage> {
+    let tree = usvg::Tree::from_data(buf, &usvg::Options::default()).ok()?;
+    let size = tree.size();
+    let (width, height) = (size.width().ceil() as u32, size.height().ceil() as u32);
+    let mut pixmap = resvg::tiny_skia::Pixmap::new(width.max(1), height.max(1))?;
+    resvg::render(&tree, resvg::tiny_skia::Transform::default(), &mut pixmap.as_mut());
+    image::RgbaImage::from_raw(pixmap.width(), pixmap.height(), pixmap.data().to_vec())
+        .map(DynamicImage::ImageRgba8)
+}
+
+/// Parse (but don't rasterize) an SVG page, so callers that can re-rasterize
+/// on demand at the display's current zoom (see `comic_reader`'s
+/// `PageImage::Vector`) get crisp output instead of being stuck with a
+/// single fixed-resolution bitmap from [`decode_page`].
+#[cfg(feature = "svg")]
+pub fn parse_svg_tree(buf: &[u8]) -> Result<usvg::Tree, ArchiveError> {
+    usvg::Tree::from_data(buf, &usvg::Options::default())
+        .map_err(|e| ArchiveError::ImageProcessingError(format!("Failed to parse SVG: {}", e)))
+}
+
+/// Rasterize a parsed SVG tree at `zoom` (1.0 = the SVG's intrinsic size)
+/// into an RGBA8 image sized to fill the resulting pixel dimensions exactly,
+/// so the page is always sharp instead of being a scaled-up fixed bitmap.
+/// Called from `ui::image::draw_vector_page` whenever the zoom bucket
+/// (`TextureCache`'s quantized `(index, zoom)` key) changes, so line art
+/// re-rasterizes at the new resolution instead of stretching a stale one.
+#[cfg(feature = "svg")]
+pub fn rasterize_svg_tree(tree: &usvg::Tree, zoom: f32) -> Option<image::RgbaImage> {
+    let base = tree.size();
+    let width = (base.width() * zoom).ceil().max(1.0) as u32;
+    let height = (base.height() * zoom).ceil().max(1.0) as u32;
+    let mut pixmap = resvg::tiny_skia::Pixmap::new(width, height)?;
+    let transform = resvg::tiny_skia::Transform::from_scale(
+        width as f32 / base.width().max(1.0),
+        height as f32 / base.height().max(1.0),
+    );
+    resvg::render(tree, transform, &mut pixmap.as_mut());
+    image::RgbaImage::from_raw(pixmap.width(), pixmap.height(), pixmap.data().to_vec())
+}
+
+#[cfg(feature = "heif")]
+fn decode_heif(buf: &[u8]) -> Option<DynamicImage> {
+    let ctx = libheif_rs::HeifContext::read_from_bytes(buf).ok()?;
+    let handle = ctx.primary_image_handle().ok()?;
+    let image = handle
+        .decode(libheif_rs::ColorSpace::Rgb(libheif_rs::RgbChroma::Rgb), None)
+        .ok()?;
+    let plane = image.planes().interleaved?;
+    let (width, height, stride) = (plane.width, plane.height, plane.stride);
+
+    // `stride` can be wider than `width * 3` bytes (e.g. row alignment
+    // padding), so copy row by row rather than assuming the plane is
+    // tightly packed.
+    let row_bytes = width as usize * 3;
+    let mut pixels = Vec::with_capacity(row_bytes * height as usize);
+    for row in 0..height as usize {
+        let start = row * stride;
+        pixels.extend_from_slice(&plane.data[start..start + row_bytes]);
+    }
+
+    image::RgbImage::from_raw(width, height, pixels).map(DynamicImage::ImageRgb8)
+}
+
+/// Decode an Aseprite document's frames, compositing each frame's layers the
+/// way the editor would export it (`asefile`'s `frame().image()` already
+/// does the layer flattening), and reading each frame's own duration as its
+/// display delay. Returns `None` on any parse/decode failure so the caller
+/// falls back to treating the file as unsupported rather than panicking.
+#[cfg(feature = "jxl")]
+fn decode_jxl(buf: &[u8]) -> Option<DynamicImage> {
+    let image = jxl_oxide::integration::JxlDecoder::new(std::io::Cursor::new(buf)).ok()?;
+    image::DynamicImage::from_decoder(image).ok()
+}
+
+fn is_jpeg(buf: &[u8]) -> bool {
+    buf.len() >= 3 && buf[0] == 0xFF && buf[1] == 0xD8 && buf[2] == 0xFF
+}
+
+/// Runtime on/off switch for the `turbo` feature's decode paths, separate
+/// from the Cargo feature itself: the feature controls whether turbojpeg is
+/// linked in at all, this controls whether it's actually used once linked,
+/// so a user can flip back to the `image` crate's decoder (e.g. to compare
+/// output, or work around a turbojpeg bug) without a rebuild. Defaults to on
+/// whenever the feature is compiled in.
+#[cfg(feature = "turbo")]
+static TURBO_ENABLED: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(true);
+
+/// Enable or disable the turbojpeg decode paths at runtime. No-op when the
+/// `turbo` feature isn't compiled in.
+#[cfg(feature = "turbo")]
+pub fn set_turbo_enabled(enabled: bool) {
+    TURBO_ENABLED.store(enabled, std::sync::atomic::Ordering::Relaxed);
+}
+
+#[cfg(feature = "turbo")]
+fn turbo_enabled() -> bool {
+    TURBO_ENABLED.load(std::sync::atomic::Ordering::Relaxed)
+}
+
+/// Decode a JPEG via libjpeg-turbo instead of the `image` crate's own
+/// (slower, pure-Rust) JPEG decoder. Most CBZ pages are JPEGs, so this is
+/// the fast path the LRU cache and `TextureCache` hit on every cache miss.
+#[cfg(feature = "turbo")]
+fn decode_jpeg_turbo(buf: &[u8]) -> Option<DynamicImage> {
+    let image: turbojpeg::Image<Vec<u8>> = turbojpeg::decompress_image(buf).ok()?;
+    image::RgbaImage::from_raw(image.width as u32, image.height as u32, image.pixels)
+        .map(DynamicImage::ImageRgba8)
+}
+
+/// Decode a JPEG at a reduced resolution using turbojpeg's built-in scaled
+/// IDCT, so thumbnail generation never pays for a full-resolution decode it
+/// would immediately downsize with [`image::imageops::resize`]. Picks the
+/// coarsest of turbojpeg's supported scaling factors (1/1, 1/2, 1/4, 1/8)
+/// that still decodes to at least `target` on its longest side, falling
+/// back to a full-resolution decode if the header can't be read.
+#[cfg(feature = "turbo")]
+fn decode_jpeg_turbo_scaled(buf: &[u8], target: u32) -> Option<DynamicImage> {
+    let header = turbojpeg::read_header(buf).ok()?;
+    let longest = header.width.max(header.height) as u32;
+    let scale = [(1, 1), (1, 2), (1, 4), (1, 8)]
+        .into_iter()
+        .filter(|(num, denom)| longest * *num as u32 / *denom as u32 >= target)
+        .last()
+        .unwrap_or((1, 1));
+
+    let mut decompressor = turbojpeg::Decompressor::new().ok()?;
+    decompressor
+        .set_scaling_factor(turbojpeg::ScalingFactor::new(scale.0, scale.1).ok()?)
+        .ok()?;
+    let image: turbojpeg::Image<Vec<u8>> = decompressor.decompress_to_image(buf).ok()?;
+    image::RgbaImage::from_raw(image.width as u32, image.height as u32, image.pixels)
+        .map(DynamicImage::ImageRgba8)
+}
+
+#[cfg(feature = "aseprite")]
+fn decode_aseprite_frames(buf: &[u8]) -> Option<Vec<Frame>> {
+    let ase = asefile::AsepriteFile::read(std::io::Cursor::new(buf)).ok()?;
+    let mut frames = Vec::with_capacity(ase.num_frames() as usize);
+    for i in 0..ase.num_frames() {
+        let frame = ase.frame(i);
+        let image = frame.image();
+        let (width, height) = (image.width(), image.height());
+        frames.push(Frame {
+            rgba: image.into_raw(),
+            width,
+            height,
+            delay_ms: frame.duration() as u16,
+        });
+    }
+    Some(frames)
+}
+
+#[cfg(feature = "raw")]
+fn decode_raw(buf: &[u8]) -> Option<DynamicImage> {
+    let raw_image = rawloader::decode(&mut std::io::Cursor::new(buf)).ok()?;
+    let mut pipeline =
+        imagepipe::Pipeline::new_from_source(imagepipe::ImageSource::Raw(raw_image)).ok()?;
+    let output = pipeline.output_8bit(None).ok()?;
+    image::RgbImage::from_raw(output.width as u32, output.height as u32, output.data)
+        .map(DynamicImage::ImageRgb8)
+}
+
+/// A single decoded animation frame: raw RGBA8 pixels plus its display
+/// delay. Static images decode to a single `Frame` with `delay_ms: 0`, so
+/// callers get a uniform animation API whether or not the source format
+/// actually supports multiple frames.
+pub struct Frame {
+    pub rgba: Vec<u8>,
+    pub width: u32,
+    pub height: u32,
+    pub delay_ms: u16,
+}
+
+fn decode_gif_frames(buf: &[u8]) -> Option<Vec<Frame>> {
+    use image::AnimationDecoder;
+    let decoder = image::codecs::gif::GifDecoder::new(std::io::Cursor::new(buf)).ok()?;
+    let frames = decoder.into_frames().collect::<Result<Vec<_>, _>>().ok()?;
+    Some(
+        frames
+            .into_iter()
+            .map(|frame| {
+                let delay_ms = frame.delay().numer_denom_ms().0 as u16;
+                let buffer = frame.into_buffer();
+                let (width, height) = (buffer.width(), buffer.height());
+                Frame {
+                    rgba: buffer.into_raw(),
+                    width,
+                    height,
+                    delay_ms,
+                }
+            })
+            .collect(),
+    )
+}
+
+#[cfg(feature = "webp_animation")]
+fn decode_webp_frames(buf: &[u8]) -> Option<Vec<Frame>> {
+    let decoder = webp_animation::Decoder::new(buf).ok()?;
+    let mut frames = Vec::new();
+    let mut prev_timestamp = 0i32;
+    for frame in decoder {
+        let timestamp = frame.timestamp();
+        let delay_ms = timestamp.saturating_sub(prev_timestamp).max(20) as u16;
+        prev_timestamp = timestamp;
+        let (width, height) = frame.dimensions();
+        frames.push(Frame {
+            rgba: frame.data().to_vec(),
+            width,
+            height,
+            delay_ms,
+        });
+    }
+    Some(frames)
+}
+
+/// Decode a page's raw bytes into its animation frames. Animated GIF and
+/// (with the `webp_animation` feature) animated WebP decode to one `Frame`
+/// per frame; everything else decodes to a single `Frame` via
+/// [`decode_page`]. Used by `ImageArchive::read_image_frames` so the
+/// viewer can play animated pages instead of only ever seeing their first
+/// frame.
+pub fn decode_frames(name: &str, buf: &[u8]) -> Result<Vec<Frame>, ArchiveError> {
+    let lower = name.to_lowercase();
+
+    if lower.ends_with(".gif") {
+        if let Some(frames) = decode_gif_frames(buf) {
+            if frames.len() > 1 {
+                return Ok(frames);
+            }
+        }
+    }
+
+    #[cfg(feature = "webp_animation")]
+    if lower.ends_with(".webp") {
+        if let Some(frames) = decode_webp_frames(buf) {
+            if frames.len() > 1 {
+                return Ok(frames);
+            }
+        }
+    }
+
+    #[cfg(feature = "aseprite")]
+    if lower.ends_with(".ase") || lower.ends_with(".aseprite") {
+        return decode_aseprite_frames(buf)
+            .filter(|frames| !frames.is_empty())
+            .ok_or_else(|| ArchiveError::ImageProcessingError("Failed to decode Aseprite file".into()));
+    }
+
+    let img = decode_page(name, buf)?;
+    let rgba = img.to_rgba8();
+    let (width, height) = rgba.dimensions();
+    Ok(vec![Frame {
+        rgba: rgba.into_raw(),
+        width,
+        height,
+        delay_ms: 0,
+    }])
+}
+
+/// Decode just the first frame of `name`, for thumbnails: animated pages
+/// are represented by their first frame rather than requiring a full
+/// multi-frame decode.
+pub fn decode_first_frame(name: &str, buf: &[u8]) -> Result<DynamicImage, ArchiveError> {
+    let frame = decode_frames(name, buf)?.remove(0);
+    image::RgbaImage::from_raw(frame.width, frame.height, frame.rgba)
+        .map(DynamicImage::ImageRgba8)
+        .ok_or_else(|| {
+            ArchiveError::ImageProcessingError("Failed to reconstruct decoded frame".into())
+        })
+}
+
+/// Like [`decode_first_frame`], but for JPEG input with the `turbo` feature
+/// decodes straight at a reduced resolution close to `target` instead of
+/// decoding full-size and letting the caller resize afterwards. Used by
+/// `ImageArchive::generate_thumbnail` so thumbnailing a JPEG-heavy archive
+/// doesn't pay for full-resolution decodes it immediately throws away.
+pub fn decode_first_frame_scaled(
+    name: &str,
+    buf: &[u8],
+    target: u32,
+) -> Result<DynamicImage, ArchiveError> {
+    #[cfg(feature = "turbo")]
+    if turbo_enabled() && is_jpeg(buf) {
+        if let Some(image) = decode_jpeg_turbo_scaled(buf, target) {
+            return Ok(image);
+        }
+        log::warn!("turbojpeg scaled decode failed for {}, falling back", name);
+    }
+    decode_first_frame(name, buf)
+}
+
+/// Decode a page's raw bytes, using `name`'s extension to pick a codec for
+/// formats the `image` crate can't handle directly (SVG, HEIC/HEIF, JPEG-XL,
+/// camera RAW). Everything else (JPEG/PNG/GIF/BMP/WebP/AVIF) goes through
+/// `image::load_from_memory`.
+pub fn decode_page(name: &str, buf: &[u8]) -> Result<DynamicImage, ArchiveError> {
+    let lower = name.to_lowercase();
+
+    #[cfg(feature = "turbo")]
+    if turbo_enabled() && is_jpeg(buf) {
+        if let Some(image) = decode_jpeg_turbo(buf) {
+            return Ok(image);
+        }
+        log::warn!("turbojpeg failed to decode {}, falling back to image crate", name);
+    }
+
+    #[cfg(feature = "svg")]
+    if lower.ends_with(".svg") {
+        return decode_svg(buf)
+            .ok_or_else(|| ArchiveError::ImageProcessingError("Failed to rasterize SVG".into()));
+    }
+
+    #[cfg(feature = "heif")]
+    if lower.ends_with(".heic") || lower.ends_with(".heif") {
+        return decode_heif(buf).ok_or_else(|| ArchiveError::Heif("Failed to decode HEIF/HEIC".into()));
+    }
+
+    #[cfg(feature = "jxl")]
+    if lower.ends_with(".jxl") {
+        return decode_jxl(buf).ok_or_else(|| ArchiveError::Jxl("Failed to decode JPEG-XL".into()));
+    }
+
+    #[cfg(feature = "raw")]
+    if is_raw_extension(&lower) {
+        return decode_raw(buf)
+            .ok_or_else(|| ArchiveError::ImageProcessingError("Failed to decode RAW image".into()));
+    }
+
+    if lower.ends_with(".avif") {
+        return image::load_from_memory(buf).map_err(|e| ArchiveError::Avif(e.to_string()));
+    }
+
+    let _ = &lower;
+    image::load_from_memory(buf)
+        .map_err(|e| ArchiveError::ImageProcessingError(format!("Failed to load image: {}", e)))
+}
+
+#[cfg(feature = "raw")]
+fn is_raw_extension(lower: &str) -> bool {
+    lower.ends_with(".cr2")
+        || lower.ends_with(".cr3")
+        || lower.ends_with(".nef")
+        || lower.ends_with(".arw")
+        || lower.ends_with(".dng")
+        || lower.ends_with(".orf")
+        || lower.ends_with(".rw2")
+        || lower.ends_with(".raf")
+}
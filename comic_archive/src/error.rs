@@ -6,10 +6,34 @@ pub enum ArchiveError {
     UnsupportedArchive,
     #[error("No images found in archive")]
     NoImages,
+    #[error("Archive is corrupt: {0}")]
+    CorruptArchive(String),
+    #[error("Archive is password-protected")]
+    Encrypted,
+    #[error("Archive uses an unsupported or unknown encryption method")]
+    UnknownEncryption,
+    #[error("Archive header is damaged")]
+    ArchiveHeaderDamaged,
+    #[error("File CRC check failed \u{2014} the archive is corrupt")]
+    FileCrcError,
+    #[error("Next volume of this multi-part archive was not found")]
+    NextVolumeNotFound,
+    #[error("Required external tool not found: install {0}")]
+    MissingTool(String),
+    #[error("Entry not found in archive")]
+    EntryNotFound,
+    #[error("{0}")]
+    RebuildRequired(String),
     #[error("Index out of bounds")]
     IndexOutOfBounds,
     #[error("Image processing error: {0}")]
     ImageProcessingError(String),
+    #[error("AVIF error: {0}")]
+    Avif(String),
+    #[error("HEIF error: {0}")]
+    Heif(String),
+    #[error("JPEG-XL error: {0}")]
+    Jxl(String),
     #[error("Manifest error: {0}")]
     ManifestError(String),
     #[error("Manifest not found")]
@@ -25,22 +25,41 @@ impl FolderImageArchive {
     }
 }
 
+/// Recursively collect every supported-format file under `dir`, relative to
+/// `root`, so nested chapter folders read as one continuous book instead of
+/// only the top-level directory. Relative paths always use `/` as the
+/// separator (matching zip/tar entry names) so `read_image_by_name`'s
+/// `self.path.join(filename)` works the same on every platform.
+fn walk_images(root: &Path, dir: &Path, out: &mut Vec<String>) {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return;
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            walk_images(root, &path, out);
+        } else if path.is_file() {
+            let name = path.file_name().unwrap().to_string_lossy().to_string();
+            if is_supported_format!(&name) {
+                let relative = path
+                    .strip_prefix(root)
+                    .unwrap_or(&path)
+                    .components()
+                    .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                    .collect::<Vec<_>>()
+                    .join("/");
+                out.push(relative);
+            }
+        }
+    }
+}
+
 #[async_trait::async_trait]
 impl ImageArchiveTrait for FolderImageArchive {
     fn list_images(&self) -> Vec<String> {
         let mut files = Vec::new();
-        if let Ok(entries) = fs::read_dir(&self.path) {
-            for entry in entries.flatten() {
-                let path = entry.path();
-                if path.is_file() {
-                    let name = path.file_name().unwrap().to_string_lossy().to_string();
-                    if is_supported_format!(&name) {
-                        files.push(name);
-                    }
-                }
-            }
-        }
-        files.sort();
+        walk_images(&self.path, &self.path, &mut files);
+        files.sort_by(|a, b| crate::sort::natural_cmp(a, b));
         files
     }
 
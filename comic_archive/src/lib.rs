@@ -1,12 +1,21 @@
-//! Unified image archive interface for CBZ, folders, RAR, and web archives.
+//! Unified image archive interface for CBZ, CBT, folders, RAR, and web archives.
 
+pub mod decode;
 pub mod error;
 pub mod model;
+pub mod net_stats;
+pub mod page_cache;
 pub mod prelude;
+pub mod sort;
+pub mod thumbnail_cache;
+pub mod web_cache;
 
 mod zip_archive;
 pub use zip_archive::ZipImageArchive;
 
+mod tar_archive;
+pub use tar_archive::TarImageArchive;
+
 mod web_archive;
 pub use web_archive::WebImageArchive;
 
@@ -25,19 +34,45 @@ pub use seven_zip_archive::SevenZipImageArchive;
 
 use image::codecs::jpeg::JpegEncoder;
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Default `generate_thumbnail` size (width and height, in pixels).
+pub const DEFAULT_THUMBNAIL_SIZE: u32 = 200;
+/// Default `generate_thumbnail` JPEG quality (1-100).
+pub const DEFAULT_THUMBNAIL_QUALITY: u8 = 80;
 
 use crate::prelude::*;
 
 #[macro_export]
 macro_rules! is_supported_format {
-    ($name:expr) => {
-        $name.ends_with(".jpg")
+    ($name:expr) => {{
+        let supported = $name.ends_with(".jpg")
             || $name.ends_with(".jpeg")
             || $name.ends_with(".png")
             || $name.ends_with(".gif")
             || $name.ends_with(".bmp")
             || $name.ends_with(".webp")
-    };
+            || $name.ends_with(".avif");
+        #[cfg(feature = "heif")]
+        let supported = supported || $name.ends_with(".heic") || $name.ends_with(".heif");
+        #[cfg(feature = "jxl")]
+        let supported = supported || $name.ends_with(".jxl");
+        #[cfg(feature = "svg")]
+        let supported = supported || $name.ends_with(".svg");
+        #[cfg(feature = "aseprite")]
+        let supported = supported || $name.ends_with(".ase") || $name.ends_with(".aseprite");
+        #[cfg(feature = "raw")]
+        let supported = supported
+            || $name.ends_with(".cr2")
+            || $name.ends_with(".cr3")
+            || $name.ends_with(".nef")
+            || $name.ends_with(".arw")
+            || $name.ends_with(".dng")
+            || $name.ends_with(".orf")
+            || $name.ends_with(".rw2")
+            || $name.ends_with(".raf");
+        supported
+    }};
 }
 
 /// Macro to simplify archive backend instantiation and manifest extraction.
@@ -88,6 +123,34 @@ pub trait ImageArchiveTrait: Send + Sync {
     async fn read_manifest_string(&self) -> Result<String, ArchiveError>;
     async fn read_manifest(&self) -> Result<Manifest, ArchiveError>;
     async fn write_manifest(&mut self, manifest: &Manifest) -> Result<(), ArchiveError>;
+
+    /// Decode `filename`'s animation frames (a single frame for static
+    /// formats). Built on top of `read_image_by_name`, so backends get it
+    /// for free and don't need to override it.
+    async fn read_image_frames(
+        &mut self,
+        filename: &str,
+    ) -> Result<Vec<crate::decode::Frame>, ArchiveError> {
+        let buf = self.read_image_by_name(filename).await?;
+        crate::decode::decode_frames(filename, &buf)
+    }
+
+    /// Shared network activity counters, if this backend fetches pages over
+    /// the network (currently only `WebImageArchive`). `None` for local
+    /// archive formats, which have nothing to report here.
+    fn net_stats(&self) -> Option<Arc<Mutex<crate::net_stats::NetStats>>> {
+        None
+    }
+
+    /// Download every page into a self-contained local CBZ at `output_path`
+    /// (the "make offline" operation). Only meaningful for backends whose
+    /// pages aren't already local, so the default errors out; `WebImageArchive`
+    /// is the only backend that overrides it.
+    fn materialize_offline(&mut self, _output_path: &Path) -> Result<(), ArchiveError> {
+        Err(ArchiveError::ManifestError(
+            "This archive has no external pages to materialize".into(),
+        ))
+    }
 }
 
 #[cfg(not(feature = "async"))]
@@ -97,6 +160,31 @@ pub trait ImageArchiveTrait: Send + Sync {
     fn read_manifest_string(&self) -> Result<String, ArchiveError>;
     fn read_manifest(&self) -> Result<Manifest, ArchiveError>;
     fn write_manifest(&mut self, manifest: &Manifest) -> Result<(), ArchiveError>;
+
+    /// Decode `filename`'s animation frames (a single frame for static
+    /// formats). Built on top of `read_image_by_name`, so backends get it
+    /// for free and don't need to override it.
+    fn read_image_frames(&mut self, filename: &str) -> Result<Vec<crate::decode::Frame>, ArchiveError> {
+        let buf = self.read_image_by_name(filename)?;
+        crate::decode::decode_frames(filename, &buf)
+    }
+
+    /// Shared network activity counters, if this backend fetches pages over
+    /// the network (currently only `WebImageArchive`). `None` for local
+    /// archive formats, which have nothing to report here.
+    fn net_stats(&self) -> Option<Arc<Mutex<crate::net_stats::NetStats>>> {
+        None
+    }
+
+    /// Download every page into a self-contained local CBZ at `output_path`
+    /// (the "make offline" operation). Only meaningful for backends whose
+    /// pages aren't already local, so the default errors out; `WebImageArchive`
+    /// is the only backend that overrides it.
+    fn materialize_offline(&mut self, _output_path: &Path) -> Result<(), ArchiveError> {
+        Err(ArchiveError::ManifestError(
+            "This archive has no external pages to materialize".into(),
+        ))
+    }
 }
 
 /// Main archive wrapper.
@@ -110,6 +198,19 @@ impl ImageArchive {
     /// Open and process an archive at the given path.
     #[cfg(feature = "async")]
     pub async fn process(path: &Path) -> Result<Self, ArchiveError> {
+        Self::process_with_password(path, None).await
+    }
+
+    /// Open and process an archive at the given path, trying `password` for
+    /// RAR/7z backends that support encryption. Other formats ignore it.
+    /// Returns `Err(ArchiveError::Encrypted)` when the archive is
+    /// password-protected and `password` is `None` or wrong, so the caller
+    /// can re-prompt.
+    #[cfg(feature = "async")]
+    pub async fn process_with_password(
+        path: &Path,
+        password: Option<&str>,
+    ) -> Result<Self, ArchiveError> {
         let ext = path
             .extension()
             .and_then(|e| e.to_str())
@@ -121,15 +222,52 @@ impl ImageArchive {
         } else {
             match ext.as_str() {
                 "cbz" | "zip" => archive_case!(ZipImageArchive, path).await,
+                "cbt" | "tar" => archive_case!(TarImageArchive, path).await,
                 #[cfg(feature = "rar")]
-                "cbr" | "rar" => archive_case!(RarImageArchive, path).await,
+                "cbr" | "rar" => {
+                    let archive = RarImageArchive::new_with_password(path, password)?;
+                    Self::finish_opening(path, archive).await
+                }
                 #[cfg(feature = "7z")]
-                "cb7" | "7z" => archive_case!(SevenZipImageArchive, path).await,
+                "cb7" | "7z" => {
+                    let archive = SevenZipImageArchive::new_with_password(path, password)?;
+                    Self::finish_opening(path, archive).await
+                }
                 _ => Err(ArchiveError::UnsupportedArchive),
             }
         }
     }
 
+    /// Read `archive`'s manifest and wrap it up into an `ImageArchive`,
+    /// shared by the password-aware RAR/7z branches of `process_with_password`
+    /// since they can't go through the path-only `archive_case!` macro.
+    #[cfg(feature = "async")]
+    async fn finish_opening<A: ImageArchiveTrait + 'static>(
+        path: &Path,
+        archive: A,
+    ) -> Result<Self, ArchiveError> {
+        let manifest = match archive.read_manifest_string().await {
+            Ok(manifest_str) => match Manifest::upgrade_from_v0_to_v1(&manifest_str) {
+                Ok(upgraded) => upgraded,
+                Err(_) => toml::from_str(&manifest_str).unwrap_or_else(|_| Manifest::default()),
+            },
+            Err(_) => Manifest::default(),
+        };
+        let is_web = manifest.meta.web_archive;
+
+        let backend: Box<dyn ImageArchiveTrait> = if is_web {
+            Box::new(WebImageArchive::new(archive, manifest.clone()))
+        } else {
+            Box::new(archive)
+        };
+
+        Ok(ImageArchive {
+            path: path.to_path_buf(),
+            manifest,
+            backend,
+        })
+    }
+
     #[cfg(not(feature = "async"))]
     pub fn process(path: &Path) -> Result<Self, ArchiveError> {
         let ext = path
@@ -177,6 +315,24 @@ impl ImageArchive {
                         backend,
                     })
                 }),
+                "cbt" | "tar" => TarImageArchive::new(path).and_then(|archive| {
+                    let manifest = match archive.read_manifest_string() {
+                        Ok(manifest_str) => {
+                            match crate::model::Manifest::upgrade_from_v0_to_v1(&manifest_str) {
+                                Ok(upgraded) => upgraded,
+                                Err(_) => toml::from_str(&manifest_str)
+                                    .unwrap_or_else(|_| Manifest::default()),
+                            }
+                        }
+                        Err(_) => Manifest::default(),
+                    };
+                    let backend: Box<dyn ImageArchiveTrait> = Box::new(archive);
+                    Ok(ImageArchive {
+                        path: path.to_path_buf(),
+                        manifest,
+                        backend,
+                    })
+                }),
                 #[cfg(feature = "rar")]
                 "cbr" | "rar" => RarImageArchive::new(path).and_then(|archive| {
                     let manifest = match archive.read_manifest_string() {
@@ -220,42 +376,65 @@ impl ImageArchive {
         }
     }
 
-    /// Generate a JPEG thumbnail for the given image in the archive.
+    /// Generate a JPEG thumbnail for the given image in the archive, sized
+    /// to `size`x`size` at `quality` (1-100).
+    ///
+    /// Backed by `thumbnail_cache`: the cache key is a SHA-256 digest of
+    /// `filename`'s raw bytes plus `size`/`quality`, so a hit skips the
+    /// decode/resize/encode entirely and repeats of the same page (e.g.
+    /// flipping back and forth, or rebuilding the thumbnail grid) are
+    /// free after the first generation.
     #[cfg(feature = "async")]
-    pub async fn generate_thumbnail(&mut self, filename: &str) -> Result<Vec<u8>, ArchiveError> {
+    pub async fn generate_thumbnail(
+        &mut self,
+        filename: &str,
+        size: u32,
+        quality: u8,
+    ) -> Result<Vec<u8>, ArchiveError> {
         let image_data = self.read_image_by_name(filename).await?;
-        let img = image::load_from_memory(&image_data).map_err(|e| {
-            ArchiveError::ImageProcessingError(format!("Failed to load image: {}", e))
-        })?;
+        let digest = thumbnail_cache::digest_hex(&image_data);
+        if let Some(cached) = thumbnail_cache::read(&digest, size, quality) {
+            return Ok(cached);
+        }
 
-        let thumbnail = img.resize(200, 200, image::imageops::FilterType::Lanczos3);
+        let img = crate::decode::decode_first_frame_scaled(filename, &image_data, size)?;
+        let thumbnail = img.resize(size, size, image::imageops::FilterType::Lanczos3);
         let mut buffer = Vec::new();
         {
-            let mut encoder = JpegEncoder::new_with_quality(&mut buffer, 80);
+            let mut encoder = JpegEncoder::new_with_quality(&mut buffer, quality);
             encoder.encode_image(&thumbnail).map_err(|e| {
                 ArchiveError::ImageProcessingError(format!("Failed to write thumbnail: {}", e))
             })?;
         }
 
+        thumbnail_cache::write(&digest, size, quality, &buffer);
         Ok(buffer)
     }
 
     #[cfg(not(feature = "async"))]
-    pub fn generate_thumbnail(&mut self, filename: &str) -> Result<Vec<u8>, ArchiveError> {
+    pub fn generate_thumbnail(
+        &mut self,
+        filename: &str,
+        size: u32,
+        quality: u8,
+    ) -> Result<Vec<u8>, ArchiveError> {
         let image_data = self.read_image_by_name(filename)?;
-        let img = image::load_from_memory(&image_data).map_err(|e| {
-            ArchiveError::ImageProcessingError(format!("Failed to load image: {}", e))
-        })?;
+        let digest = thumbnail_cache::digest_hex(&image_data);
+        if let Some(cached) = thumbnail_cache::read(&digest, size, quality) {
+            return Ok(cached);
+        }
 
-        let thumbnail = img.resize(200, 200, image::imageops::FilterType::Lanczos3);
+        let img = crate::decode::decode_first_frame_scaled(filename, &image_data, size)?;
+        let thumbnail = img.resize(size, size, image::imageops::FilterType::Lanczos3);
         let mut buffer = Vec::new();
         {
-            let mut encoder = JpegEncoder::new_with_quality(&mut buffer, 80);
+            let mut encoder = JpegEncoder::new_with_quality(&mut buffer, quality);
             encoder.encode_image(&thumbnail).map_err(|e| {
                 ArchiveError::ImageProcessingError(format!("Failed to write thumbnail: {}", e))
             })?;
         }
 
+        thumbnail_cache::write(&digest, size, quality, &buffer);
         Ok(buffer)
     }
 
@@ -273,6 +452,22 @@ impl ImageArchive {
         self.backend.read_image_by_name(filename)
     }
 
+    /// Decode `filename`'s animation frames (a single frame for static
+    /// formats), for playing animated GIF/WebP pages rather than only
+    /// ever showing their first frame.
+    #[cfg(feature = "async")]
+    pub async fn read_image_frames(
+        &mut self,
+        filename: &str,
+    ) -> Result<Vec<crate::decode::Frame>, ArchiveError> {
+        self.backend.read_image_frames(filename).await
+    }
+
+    #[cfg(not(feature = "async"))]
+    pub fn read_image_frames(&mut self, filename: &str) -> Result<Vec<crate::decode::Frame>, ArchiveError> {
+        self.backend.read_image_frames(filename)
+    }
+
     #[cfg(feature = "async")]
     pub async fn read_image_by_index(&mut self, index: usize) -> Result<Vec<u8>, ArchiveError> {
         let filenames = self.list_images();
@@ -334,4 +529,16 @@ impl ImageArchive {
     pub fn path(&self) -> &Path {
         self.path.as_path()
     }
+
+    /// Shared network activity counters for this archive, if its backend
+    /// fetches pages over the network. See `net_stats::NetStats`.
+    pub fn net_stats(&self) -> Option<Arc<Mutex<net_stats::NetStats>>> {
+        self.backend.net_stats()
+    }
+
+    /// Download every page into a self-contained local CBZ at `output_path`.
+    /// Only web archives support this; see `ImageArchiveTrait::materialize_offline`.
+    pub fn materialize_offline(&mut self, output_path: &Path) -> Result<(), ArchiveError> {
+        self.backend.materialize_offline(output_path)
+    }
 }
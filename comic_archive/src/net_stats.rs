@@ -0,0 +1,63 @@
+//! Byte/request counters for `WebImageArchive`, so the debug UI can show
+//! real network activity for remote archives instead of OS-level interface
+//! counters (which `sysinfo` can't attribute to a single process's HTTP
+//! requests).
+
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+/// How long a bytes-downloaded sample stays in the rolling window used to
+/// compute `bytes_per_sec`.
+const RATE_WINDOW: Duration = Duration::from_secs(5);
+
+/// Per-URL fetch history.
+#[derive(Default, Clone)]
+pub struct UrlStat {
+    pub bytes: u64,
+    pub requests: u64,
+    pub cache_hits: u64,
+}
+
+/// Shared network activity counters for a single `WebImageArchive`. Cheap
+/// to clone the `Arc` around; callers lock it only to read or record a
+/// fetch.
+#[derive(Default)]
+pub struct NetStats {
+    pub per_url: HashMap<String, UrlStat>,
+    pub total_bytes: u64,
+    pub total_requests: u64,
+    pub total_cache_hits: u64,
+    /// `(when, bytes downloaded in that fetch)`, pruned to `RATE_WINDOW` on
+    /// every read so `bytes_per_sec` reflects recent activity rather than
+    /// the whole session.
+    samples: Vec<(Instant, u64)>,
+}
+
+impl NetStats {
+    /// Record a completed fetch of `url`. `from_cache` true means the body
+    /// was served from `web_cache` (see `crate::web_cache`) and no bytes
+    /// actually crossed the network, so it's counted toward cache hits but
+    /// not the download-rate sample window.
+    pub fn record_fetch(&mut self, url: &str, bytes: u64, from_cache: bool) {
+        let entry = self.per_url.entry(url.to_string()).or_default();
+        entry.bytes += bytes;
+        entry.requests += 1;
+        self.total_requests += 1;
+        if from_cache {
+            entry.cache_hits += 1;
+            self.total_cache_hits += 1;
+        } else {
+            self.total_bytes += bytes;
+            self.samples.push((Instant::now(), bytes));
+        }
+    }
+
+    /// Approximate current download rate, in bytes/sec, averaged over the
+    /// last `RATE_WINDOW` of actual (non-cached) fetches.
+    pub fn bytes_per_sec(&mut self) -> f64 {
+        let cutoff = Instant::now() - RATE_WINDOW;
+        self.samples.retain(|(when, _)| *when >= cutoff);
+        let total: u64 = self.samples.iter().map(|(_, bytes)| bytes).sum();
+        total as f64 / RATE_WINDOW.as_secs_f64()
+    }
+}
@@ -0,0 +1,162 @@
+//! Disk-backed second tier for `SharedImageCache`: decoded page RGBA bytes
+//! survive between sessions, so reopening the same archive can skip
+//! re-decoding pages it already decoded last time.
+//!
+//! Keyed by a SHA-256 digest of the archive path, entry name, the archive
+//! file's mtime (so an edited/replaced archive invalidates its entries),
+//! and the decode parameters (currently just the downsample target),
+//! mirroring `thumbnail_cache`'s content-addressed layout but under its own
+//! `pages` subdirectory so the two caches can be cleared independently.
+//!
+//! Entries are flat `<digest>.rgba` files: an 8-byte `(width, height)`
+//! header (u32 LE each) followed by raw RGBA8 pixels. `enforce_budget` runs
+//! after every write and deletes the oldest-by-mtime entries until the
+//! directory is back under `PAGE_CACHE_BUDGET_BYTES`.
+
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Total on-disk size this cache is allowed to grow to before
+/// `enforce_budget` starts evicting the oldest entries.
+const PAGE_CACHE_BUDGET_BYTES: u64 = 1024 * 1024 * 1024;
+
+fn cache_root() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("comic_suite")
+        .join("pages")
+}
+
+/// Cache key for `entry_name` inside the archive at `archive_path`, decoded
+/// with `max_dimension`. `mtime` is folded in so replacing the archive file
+/// invalidates every entry that came from it.
+pub fn key(
+    archive_path: &Path,
+    entry_name: &str,
+    mtime: Option<SystemTime>,
+    max_dimension: Option<u32>,
+) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(archive_path.to_string_lossy().as_bytes());
+    hasher.update(b"\0");
+    hasher.update(entry_name.as_bytes());
+    hasher.update(b"\0");
+    if let Some(secs) = mtime
+        .and_then(|t| t.duration_since(SystemTime::UNIX_EPOCH).ok())
+        .map(|d| d.as_secs())
+    {
+        hasher.update(secs.to_le_bytes());
+    }
+    hasher.update(max_dimension.unwrap_or(0).to_le_bytes());
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn cache_path(key: &str) -> PathBuf {
+    cache_root().join(format!("{key}.rgba"))
+}
+
+/// Read a cached page back as `(width, height, rgba_bytes)`, if present.
+pub fn read(key: &str) -> Option<(u32, u32, Vec<u8>)> {
+    let bytes = std::fs::read(cache_path(key)).ok()?;
+    if bytes.len() < 8 {
+        return None;
+    }
+    let width = u32::from_le_bytes(bytes[0..4].try_into().ok()?);
+    let height = u32::from_le_bytes(bytes[4..8].try_into().ok()?);
+    Some((width, height, bytes[8..].to_vec()))
+}
+
+/// Write a decoded page into the cache, then enforce the size budget.
+/// Failures are non-fatal: the page was already decoded and returned to the
+/// caller, so a write error only costs a future cache miss.
+pub fn write(key: &str, width: u32, height: u32, rgba: &[u8]) {
+    let path = cache_path(key);
+    let Some(parent) = path.parent() else {
+        return;
+    };
+    if let Err(e) = std::fs::create_dir_all(parent) {
+        log::warn!("couldn't create page cache dir: {e}");
+        return;
+    }
+    let mut bytes = Vec::with_capacity(8 + rgba.len());
+    bytes.extend_from_slice(&width.to_le_bytes());
+    bytes.extend_from_slice(&height.to_le_bytes());
+    bytes.extend_from_slice(rgba);
+    if let Err(e) = std::fs::write(&path, &bytes) {
+        log::warn!("couldn't write page cache entry {:?}: {e}", path);
+        return;
+    }
+    enforce_budget();
+}
+
+/// Delete the oldest-by-mtime entries until the cache directory's total
+/// size is back under `PAGE_CACHE_BUDGET_BYTES`.
+fn enforce_budget() {
+    let Ok(entries) = std::fs::read_dir(cache_root()) else {
+        return;
+    };
+    let mut files: Vec<(PathBuf, u64, SystemTime)> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let meta = e.metadata().ok()?;
+            let modified = meta.modified().ok()?;
+            Some((e.path(), meta.len(), modified))
+        })
+        .collect();
+
+    let total: u64 = files.iter().map(|(_, len, _)| len).sum();
+    if total <= PAGE_CACHE_BUDGET_BYTES {
+        return;
+    }
+
+    let mut remaining = total;
+    files.sort_by_key(|(_, _, modified)| *modified);
+    for (path, len, _) in files {
+        if remaining <= PAGE_CACHE_BUDGET_BYTES {
+            break;
+        }
+        if std::fs::remove_file(&path).is_ok() {
+            remaining = remaining.saturating_sub(len);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn key_changes_when_the_mtime_or_max_dimension_changes() {
+        let path = Path::new("/comics/one.cbz");
+        let base = key(path, "page_001.jpg", None, None);
+
+        assert_ne!(base, key(path, "page_001.jpg", Some(SystemTime::now()), None));
+        assert_ne!(base, key(path, "page_001.jpg", None, Some(1024)));
+        assert_eq!(base, key(path, "page_001.jpg", None, None));
+    }
+
+    #[test]
+    fn write_then_read_round_trips_dimensions_and_pixels() {
+        let k = key(Path::new("/comics/roundtrip.cbz"), "page_001.jpg", None, None);
+        let rgba = vec![1u8, 2, 3, 4, 5, 6, 7, 8];
+
+        write(&k, 1, 2, &rgba);
+        let (width, height, read_back) = read(&k).expect("just-written entry should read back");
+
+        assert_eq!((width, height), (1, 2));
+        assert_eq!(read_back, rgba);
+
+        std::fs::remove_file(cache_path(&k)).ok();
+    }
+
+    #[test]
+    fn read_returns_none_for_a_key_that_was_never_written() {
+        let k = key(Path::new("/comics/missing.cbz"), "page_999.jpg", None, None);
+        assert!(read(&k).is_none());
+    }
+}
@@ -2,6 +2,14 @@
 pub use crate::RarImageArchive;
 #[cfg(feature = "7z")]
 pub use crate::SevenZipImageArchive;
+pub use crate::decode::{Frame, decode_page};
+#[cfg(feature = "svg")]
+pub use crate::decode::{parse_svg_tree, rasterize_svg_tree};
+#[cfg(feature = "svg")]
+pub use usvg;
 pub use crate::error::ArchiveError;
 pub use crate::model::{ExternalPages, Manifest, Metadata};
-pub use crate::{ImageArchive, ImageArchiveTrait, WebImageArchive, ZipImageArchive};
+pub use crate::{
+    DEFAULT_THUMBNAIL_QUALITY, DEFAULT_THUMBNAIL_SIZE, ImageArchive, ImageArchiveTrait,
+    TarImageArchive, WebImageArchive, ZipImageArchive,
+};
@@ -1,3 +1,10 @@
+//! Fallback RAR/CBR backend that shells out to the external `unrar`/`rar`
+//! binaries. Used in place of [`super::native::RarImageArchive`] when the
+//! `rar_cli` feature is enabled, e.g. where a native libunrar build isn't
+//! available. One `unrar x` call per page means a tempdir round-trip per
+//! read, and the `unrar l` output is parsed as fragile whitespace-delimited
+//! text, so prefer the native backend unless this is your only option.
+
 use crate::is_supported_format;
 use crate::prelude::*;
 use std::fs;
@@ -16,12 +23,39 @@ const CREATE_NO_WINDOW: u32 = 0x08000000;
 pub struct RarImageArchive {
     path: PathBuf,
     entries: Vec<String>,
+    password: Option<String>,
+}
+
+/// Build the `-p<password>` arg for an `unrar`/`rar` invocation, or `-p-` to
+/// disable the password prompt so an encrypted archive fails fast instead of
+/// hanging the call waiting for interactive input.
+fn password_arg(password: &Option<String>) -> String {
+    match password {
+        Some(pw) => format!("-p{pw}"),
+        None => "-p-".to_string(),
+    }
+}
+
+/// Does this `unrar`/`rar` stderr/stdout output indicate a missing or wrong
+/// password, as opposed to some other failure?
+fn looks_like_password_failure(text: &str) -> bool {
+    let lower = text.to_lowercase();
+    lower.contains("password") || lower.contains("encrypted")
 }
 
 impl RarImageArchive {
     pub fn new(path: &Path) -> Result<Self, ArchiveError> {
+        Self::new_with_password(path, None)
+    }
+
+    /// Open a possibly password-protected RAR/CBR archive.
+    pub fn new_with_password(path: &Path, password: Option<&str>) -> Result<Self, ArchiveError> {
+        let password = password.map(str::to_string);
         let mut cmd = Command::new("unrar");
-        cmd.arg("l").arg("-c-").arg(path);
+        cmd.arg("l")
+            .arg("-c-")
+            .arg(password_arg(&password))
+            .arg(path);
 
         #[cfg(windows)]
         cmd.creation_flags(CREATE_NO_WINDOW);
@@ -31,6 +65,10 @@ impl RarImageArchive {
             .map_err(|_| ArchiveError::UnsupportedArchive)?;
 
         if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if looks_like_password_failure(&stderr) {
+                return Err(ArchiveError::Encrypted);
+            }
             return Err(ArchiveError::UnsupportedArchive);
         }
 
@@ -58,11 +96,12 @@ impl RarImageArchive {
                 }
             }
         }
-        entries.sort();
+        entries.sort_by(|a, b| crate::sort::natural_cmp(a, b));
 
         Ok(Self {
             path: path.to_path_buf(),
             entries,
+            password,
         })
     }
 
@@ -77,6 +116,7 @@ impl RarImageArchive {
         let mut cmd = Command::new("unrar");
         cmd.arg("x")
             .arg("-y")
+            .arg(password_arg(&self.password))
             .arg(&self.path)
             .arg(filename)
             .arg(tmp_dir.path());
@@ -84,11 +124,15 @@ impl RarImageArchive {
         #[cfg(windows)]
         cmd.creation_flags(CREATE_NO_WINDOW);
 
-        let status = cmd
-            .status()
+        let output = cmd
+            .output()
             .map_err(|_| ArchiveError::UnsupportedArchive)?;
 
-        if !status.success() {
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if looks_like_password_failure(&stderr) {
+                return Err(ArchiveError::Encrypted);
+            }
             return Err(ArchiveError::UnsupportedArchive);
         }
 
@@ -108,6 +152,7 @@ impl RarImageArchive {
         let mut cmd = Command::new("unrar");
         cmd.arg("x")
             .arg("-y")
+            .arg(password_arg(&self.password))
             .arg(&self.path)
             .arg("manifest.toml")
             .arg(tmp_dir.path());
@@ -115,11 +160,15 @@ impl RarImageArchive {
         #[cfg(windows)]
         cmd.creation_flags(CREATE_NO_WINDOW);
 
-        let status = cmd
-            .status()
+        let output = cmd
+            .output()
             .map_err(|_| ArchiveError::ManifestError("Failed to run unrar".into()))?;
 
-        if !status.success() {
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            if looks_like_password_failure(&stderr) {
+                return Err(ArchiveError::Encrypted);
+            }
             return Err(ArchiveError::ManifestError(
                 "manifest.toml not found in archive".into(),
             ));
@@ -145,12 +194,14 @@ impl ImageArchiveTrait for RarImageArchive {
 
     async fn read_image_by_name(&mut self, filename: &str) -> Result<Vec<u8>, ArchiveError> {
         let path = self.path.clone();
+        let password = self.password.clone();
         let filename = filename.to_string();
         tokio::task::spawn_blocking(move || {
             let mut archive = RarImageArchive {
                 path,
-                entries: Vec::new(),
-            }; // entries unused
+                entries: Vec::new(), // entries unused
+                password,
+            };
             archive.read_image_by_name_sync(&filename)
         })
         .await
@@ -159,10 +210,12 @@ impl ImageArchiveTrait for RarImageArchive {
 
     async fn read_manifest_string(&self) -> Result<String, ArchiveError> {
         let path = self.path.clone();
+        let password = self.password.clone();
         tokio::task::spawn_blocking(move || {
             let archive = RarImageArchive {
                 path,
                 entries: Vec::new(),
+                password,
             };
             archive.read_manifest_string_sync()
         })
@@ -179,6 +232,7 @@ impl ImageArchiveTrait for RarImageArchive {
 
     async fn write_manifest(&mut self, manifest: &Manifest) -> Result<(), ArchiveError> {
         let path = self.path.clone();
+        let password = self.password.clone();
         let toml = toml::to_string_pretty(manifest)
             .map_err(|e| ArchiveError::ManifestError(format!("Invalid TOML: {}", e)))?;
         tokio::task::spawn_blocking(move || {
@@ -191,6 +245,7 @@ impl ImageArchiveTrait for RarImageArchive {
             let mut cmd = Command::new("rar");
             cmd.arg("u")
                 .arg("-ep1")
+                .arg(password_arg(&password))
                 .arg(&path)
                 .arg(&manifest_path);
 
@@ -234,66 +289,11 @@ impl ImageArchiveTrait for RarImageArchive {
     ///
     /// A vector of bytes containing the image data, or an `ArchiveError` on failure.
     fn read_image_by_name(&mut self, filename: &str) -> Result<Vec<u8>, ArchiveError> {
-        let tmp_dir = tempdir().map_err(|_| {
-            ArchiveError::Io(std::io::Error::new(
-                std::io::ErrorKind::Other,
-                "Failed to create temp dir",
-            ))
-        })?;
-        let mut cmd = Command::new("unrar");
-        cmd.arg("x")
-            .arg("-y") // assume yes
-            .arg(&self.path)
-            .arg(filename)
-            .arg(tmp_dir.path());
-
-        #[cfg(windows)]
-        cmd.creation_flags(CREATE_NO_WINDOW);
-
-        let status = cmd
-            .status()
-            .map_err(|_| ArchiveError::UnsupportedArchive)?;
-
-        if !status.success() {
-            return Err(ArchiveError::UnsupportedArchive);
-        }
-
-        let extracted_path = tmp_dir.path().join(filename);
-        let mut file = fs::File::open(&extracted_path).map_err(|_| ArchiveError::NoImages)?;
-        let mut buffer = Vec::new();
-        file.read_to_end(&mut buffer)
-            .map_err(|_| ArchiveError::NoImages)?;
-
-        Ok(buffer)
+        self.read_file_by_name_sync(filename)
     }
 
     fn read_manifest_string(&self) -> Result<String, ArchiveError> {
-        let tmp_dir =
-            tempdir().map_err(|_| ArchiveError::ManifestError("Tempdir failed".into()))?;
-        let mut cmd = Command::new("unrar");
-        cmd.arg("x")
-            .arg("-y")
-            .arg(&self.path)
-            .arg("manifest.toml")
-            .arg(tmp_dir.path());
-
-        #[cfg(windows)]
-        cmd.creation_flags(CREATE_NO_WINDOW);
-
-        let status = cmd
-            .status()
-            .map_err(|_| ArchiveError::ManifestError("Failed to run unrar".into()))?;
-
-        if !status.success() {
-            return Err(ArchiveError::ManifestError(
-                "manifest.toml not found in archive".into(),
-            ));
-        }
-
-        let manifest_path = tmp_dir.path().join("manifest.toml");
-        let manifest_str = fs::read_to_string(&manifest_path)
-            .map_err(|_| ArchiveError::ManifestError("Failed to read manifest.toml".into()))?;
-        Ok(manifest_str)
+        self.read_manifest_string_sync()
     }
 
     /// Read and parse the manifest from the RAR archive.
@@ -347,6 +347,7 @@ impl ImageArchiveTrait for RarImageArchive {
         let mut cmd = Command::new("rar");
         cmd.arg("u") // update
             .arg("-ep1") // exclude base dir from names
+            .arg(password_arg(&self.password))
             .arg(&self.path)
             .arg(&manifest_path);
 
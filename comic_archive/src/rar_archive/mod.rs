@@ -0,0 +1,17 @@
+//! RAR/CBR archive backend.
+//!
+//! Defaults to [`native::RarImageArchive`], which talks to libunrar
+//! in-process via the `unrar` crate. Enable the `rar_cli` feature to select
+//! [`cli::RarImageArchive`] instead, which shells out to the `unrar`/`rar`
+//! binaries — useful as a fallback where a native libunrar build isn't
+//! available.
+
+#[cfg(feature = "rar_cli")]
+mod cli;
+#[cfg(feature = "rar_cli")]
+pub use cli::RarImageArchive;
+
+#[cfg(not(feature = "rar_cli"))]
+mod native;
+#[cfg(not(feature = "rar_cli"))]
+pub use native::RarImageArchive;
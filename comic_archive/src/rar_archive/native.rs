@@ -0,0 +1,197 @@
+//! Native libunrar backend, via the `unrar` crate's bindings to
+//! `unrar_sys`. Listing walks the archive's header chain in-process and
+//! `read_image_by_name` decompresses a single entry straight into memory —
+//! no tempdir, no `unrar`/`rar` binaries on PATH.
+//!
+//! libunrar's archive handle is a forward-only cursor: you read a header,
+//! then either skip or extract that entry, and each step hands back a new
+//! handle positioned at the next header. There's no seeking to an entry by
+//! index, so `read_image_by_name` re-opens the archive and walks headers
+//! from the start until the filename matches.
+
+use crate::error::ArchiveError;
+use crate::is_supported_format;
+use crate::prelude::*;
+use std::path::{Path, PathBuf};
+use unrar::error::{Code, UnrarError};
+use unrar::Archive;
+
+pub struct RarImageArchive {
+    path: PathBuf,
+    entries: Vec<String>,
+    password: Option<String>,
+}
+
+impl RarImageArchive {
+    pub fn new(path: &Path) -> Result<Self, ArchiveError> {
+        Self::new_with_password(path, None)
+    }
+
+    /// Open a possibly password-protected RAR/CBR archive. `password` is
+    /// retried on every header read, since libunrar can't cache it across
+    /// the per-entry re-opens `read_entry_by_name` performs.
+    pub fn new_with_password(path: &Path, password: Option<&str>) -> Result<Self, ArchiveError> {
+        let mut open = Archive::new(path);
+        if let Some(pw) = password {
+            open = open.password(pw);
+        }
+        let archive = open.open_for_listing().map_err(map_unrar_err)?;
+
+        let mut entries = Vec::new();
+        for entry in archive {
+            let entry = entry.map_err(map_unrar_err)?;
+            if entry.is_directory() {
+                continue;
+            }
+            let filename = entry.filename.to_string_lossy().to_string();
+            let filename_lower = filename.to_lowercase();
+            if is_supported_format!(&filename_lower) {
+                entries.push(filename);
+            }
+        }
+        entries.sort_by(|a, b| crate::sort::natural_cmp(a, b));
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            entries,
+            password: password.map(str::to_string),
+        })
+    }
+
+    fn read_file_by_name_sync(&self, filename: &str) -> Result<Vec<u8>, ArchiveError> {
+        read_entry_by_name(&self.path, self.password.as_deref(), filename)
+    }
+
+    fn read_manifest_string_sync(&self) -> Result<String, ArchiveError> {
+        let data = read_entry_by_name(&self.path, self.password.as_deref(), "manifest.toml")?;
+        String::from_utf8(data)
+            .map_err(|_| ArchiveError::ManifestError("manifest.toml is not valid UTF-8".into()))
+    }
+}
+
+/// Decompress `filename` into memory by opening the archive and walking
+/// headers from the start until it matches — libunrar can't seek directly
+/// to an entry. Takes the archive path and password alone (not
+/// `&RarImageArchive`) since `entries` plays no part in the lookup, which
+/// also lets the `spawn_blocking` call sites below read straight off the
+/// cloned path instead of constructing a throwaway archive value just to
+/// reach this.
+fn read_entry_by_name(
+    path: &Path,
+    password: Option<&str>,
+    filename: &str,
+) -> Result<Vec<u8>, ArchiveError> {
+    let mut open = Archive::new(path);
+    if let Some(pw) = password {
+        open = open.password(pw);
+    }
+    let mut archive = open.open_for_processing().map_err(map_unrar_err)?;
+
+    while let Some(header) = archive.read_header().map_err(map_unrar_err)? {
+        let is_match =
+            !header.entry().is_directory() && header.entry().filename.to_string_lossy() == filename;
+        if is_match {
+            let (data, _rest) = header.read().map_err(map_unrar_err)?;
+            return Ok(data);
+        }
+        archive = header.skip().map_err(map_unrar_err)?;
+    }
+
+    Err(ArchiveError::EntryNotFound)
+}
+
+/// Translate a libunrar error code into our typed `ArchiveError`, instead of
+/// collapsing everything to `UnsupportedArchive`.
+fn map_unrar_err<T>(err: UnrarError<T>) -> ArchiveError {
+    match err.code {
+        Code::BadArchive => ArchiveError::ArchiveHeaderDamaged,
+        Code::BadData => ArchiveError::FileCrcError,
+        Code::UnknownFormat => ArchiveError::UnsupportedArchive,
+        Code::Password | Code::MissingPassword | Code::BadPassword => ArchiveError::Encrypted,
+        other => ArchiveError::Other(format!("libunrar error: {:?}", other)),
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl ImageArchiveTrait for RarImageArchive {
+    fn list_images(&self) -> Vec<String> {
+        self.entries.clone()
+    }
+
+    fn read_image_by_name_sync(&mut self, filename: &str) -> Result<Vec<u8>, ArchiveError> {
+        self.read_file_by_name_sync(filename)
+    }
+
+    async fn read_image_by_name(&mut self, filename: &str) -> Result<Vec<u8>, ArchiveError> {
+        let path = self.path.clone();
+        let password = self.password.clone();
+        let filename = filename.to_string();
+        tokio::task::spawn_blocking(move || {
+            read_entry_by_name(&path, password.as_deref(), &filename)
+        })
+        .await
+        .unwrap_or_else(|e| Err(ArchiveError::Other(format!("Join error: {e}"))))
+    }
+
+    async fn read_manifest_string(&self) -> Result<String, ArchiveError> {
+        let path = self.path.clone();
+        let password = self.password.clone();
+        tokio::task::spawn_blocking(move || {
+            let data = read_entry_by_name(&path, password.as_deref(), "manifest.toml")?;
+            String::from_utf8(data).map_err(|_| {
+                ArchiveError::ManifestError("manifest.toml is not valid UTF-8".into())
+            })
+        })
+        .await
+        .unwrap_or_else(|e| Err(ArchiveError::Other(format!("Join error: {e}"))))
+    }
+
+    async fn read_manifest(&self) -> Result<Manifest, ArchiveError> {
+        let manifest_str = self.read_manifest_string().await?;
+        let manifest: Manifest = toml::from_str(&manifest_str)
+            .map_err(|e| ArchiveError::ManifestError(format!("Invalid TOML: {}", e)))?;
+        Ok(manifest)
+    }
+
+    async fn write_manifest(&mut self, _manifest: &Manifest) -> Result<(), ArchiveError> {
+        // libunrar has no archive-writing API, so there's no way to patch
+        // manifest.toml in place like the CLI fallback does via `rar u`.
+        // Surface that plainly rather than pretending WinRAR is required
+        // (as the CLI path does) when rebuilding the archive is the only
+        // real option here.
+        Err(ArchiveError::RebuildRequired(
+            "the native RAR backend can't write archives; rebuild the archive to update its manifest"
+                .into(),
+        ))
+    }
+}
+
+#[cfg(not(feature = "async"))]
+impl ImageArchiveTrait for RarImageArchive {
+    fn list_images(&self) -> Vec<String> {
+        self.entries.clone()
+    }
+
+    fn read_image_by_name(&mut self, filename: &str) -> Result<Vec<u8>, ArchiveError> {
+        self.read_file_by_name_sync(filename)
+    }
+
+    fn read_manifest_string(&self) -> Result<String, ArchiveError> {
+        self.read_manifest_string_sync()
+    }
+
+    fn read_manifest(&self) -> Result<Manifest, ArchiveError> {
+        let manifest_str = self.read_manifest_string()?;
+        let manifest: Manifest = toml::from_str(&manifest_str)
+            .map_err(|e| ArchiveError::ManifestError(format!("Invalid TOML: {}", e)))?;
+        Ok(manifest)
+    }
+
+    fn write_manifest(&mut self, _manifest: &Manifest) -> Result<(), ArchiveError> {
+        Err(ArchiveError::RebuildRequired(
+            "the native RAR backend can't write archives; rebuild the archive to update its manifest"
+                .into(),
+        ))
+    }
+}
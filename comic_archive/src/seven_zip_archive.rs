@@ -1,94 +1,251 @@
 use crate::error::*;
 use crate::is_supported_format;
 use crate::prelude::*;
+use lru::LruCache;
 use std::fs;
-use std::io::Read;
+use std::num::NonZeroUsize;
 use std::os::windows::process::CommandExt;
 use std::path::{Path, PathBuf};
-use std::process::Command;
-use tempfile::TempDir;
-use walkdir;
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+use tempfile::tempdir;
 
 const CREATE_NO_WINDOW: u32 = 0x08000000;
 
+/// How many extracted pages to keep in memory, so re-reading the page
+/// currently on screen doesn't re-invoke `7z e` every frame.
+const PAGE_CACHE_SIZE: usize = 4;
+
 pub struct SevenZipImageArchive {
-    #[allow(dead_code)]
     path: PathBuf,
     entries: Vec<String>,
-    temp_dir: TempDir,
+    password: Option<String>,
+    page_cache: Arc<Mutex<LruCache<String, Vec<u8>>>>,
 }
 
 impl SevenZipImageArchive {
     pub fn new(path: &Path) -> Result<Self, ArchiveError> {
-        let temp_dir = tempfile::tempdir().map_err(|_| ArchiveError::NoImages)?;
-        log::info!("Extracting all files from archive: {:?}", path);
+        Self::new_with_password(path, None)
+    }
 
-        let mut cmd = Command::new("7z");
-        cmd.arg("x")
-            .arg(path)
-            .arg(format!("-o{}", temp_dir.path().display()));
+    /// Open a possibly password-protected CB7/7z archive.
+    pub fn new_with_password(path: &Path, password: Option<&str>) -> Result<Self, ArchiveError> {
+        let password = password.map(str::to_string);
+        let entries = list_entries(path, password.as_deref())?;
+        Ok(Self {
+            path: path.to_path_buf(),
+            entries,
+            password,
+            page_cache: Arc::new(Mutex::new(LruCache::new(
+                NonZeroUsize::new(PAGE_CACHE_SIZE).unwrap(),
+            ))),
+        })
+    }
 
-        #[cfg(windows)]
-        {
-            cmd.creation_flags(CREATE_NO_WINDOW);
+    fn read_file_by_name_sync(&self, filename: &str) -> Result<Vec<u8>, ArchiveError> {
+        read_entry_cached(&self.path, self.password.as_deref(), &self.page_cache, filename)
+    }
+}
+
+/// Add the `-p<password>` switch when a password is given, and otherwise
+/// close stdin so a password-protected archive fails fast with "wrong
+/// password" instead of 7z blocking on an interactive prompt.
+fn apply_password(cmd: &mut Command, password: Option<&str>) {
+    match password {
+        Some(pw) => {
+            cmd.arg(format!("-p{pw}"));
         }
+        None => {
+            cmd.stdin(Stdio::null());
+        }
+    }
+}
+
+/// List an archive's entries via `7z l -slt`'s machine-parseable key/value
+/// output (exact paths and sizes, no column-width guessing) instead of
+/// extracting every file up front just to learn their names.
+fn list_entries(path: &Path, password: Option<&str>) -> Result<Vec<String>, ArchiveError> {
+    let mut cmd = Command::new("7z");
+    cmd.arg("l").arg("-slt").arg(path);
+    apply_password(&mut cmd, password);
 
-        let status = cmd.status().map_err(|_| ArchiveError::NoImages)?;
+    #[cfg(windows)]
+    {
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
 
-        if !status.success() {
-            log::info!("7z extraction failed for {:?}", path);
-            return Err(ArchiveError::NoImages);
+    let output = cmd.output().map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            ArchiveError::MissingTool("7z".into())
+        } else {
+            ArchiveError::Io(e)
         }
+    })?;
 
-        // Recursively collect all supported image files from temp_dir
-        let mut entries = Vec::new();
-        for entry in walkdir::WalkDir::new(temp_dir.path())
-            .into_iter()
-            .filter_map(|e| e.ok())
-            .filter(|e| e.file_type().is_file())
-        {
-            let rel_path = entry
-                .path()
-                .strip_prefix(temp_dir.path())
-                .unwrap()
-                .to_string_lossy()
-                .to_string();
-            let rel_path_lower = rel_path.to_lowercase();
-            log::info!("found extracted file: '{}'", rel_path);
-            if is_supported_format!(&rel_path_lower) {
-                log::info!("accepted image: '{}'", rel_path);
-                entries.push(rel_path);
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        log::info!("7z listing failed for {:?}: {}", path, stderr);
+        return Err(classify_7z_failure(&stderr));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut entries = Vec::new();
+    let mut listing_started = false;
+    let mut current_path: Option<String> = None;
+    let mut is_dir = false;
+
+    for line in stdout.lines() {
+        if !listing_started {
+            if line.trim_start().starts_with("----------") {
+                listing_started = true;
+            }
+            continue;
+        }
+        if line.is_empty() {
+            if let Some(name) = current_path.take() {
+                push_if_supported(&mut entries, name, is_dir);
             }
+            is_dir = false;
+            continue;
         }
-        entries.sort();
-        log::info!("Archive entries: {:?}", entries);
+        if let Some(value) = line.strip_prefix("Path = ") {
+            current_path = Some(value.to_string());
+        } else if let Some(value) = line.strip_prefix("Attributes = ") {
+            is_dir = value.contains('D');
+        }
+    }
+    if let Some(name) = current_path.take() {
+        push_if_supported(&mut entries, name, is_dir);
+    }
 
-        Ok(Self {
-            path: path.to_path_buf(),
-            entries,
-            temp_dir,
-        })
+    entries.sort_by(|a, b| crate::sort::natural_cmp(a, b));
+    log::info!("Archive entries: {:?}", entries);
+    Ok(entries)
+}
+
+fn push_if_supported(entries: &mut Vec<String>, name: String, is_dir: bool) {
+    let name_lower = name.to_lowercase();
+    if !is_dir && is_supported_format!(&name_lower) {
+        entries.push(name);
     }
+}
 
-    fn read_file_by_name_sync(&self, filename: &str) -> Result<Vec<u8>, ArchiveError> {
-        let extracted_path = self.temp_dir.path().join(filename);
-        log::info!("Reading extracted file at {:?}", extracted_path);
+/// Read `filename`'s bytes from the page cache, or extract just that entry
+/// with `7z e -so` (streaming its data straight to stdout) and cache the
+/// result, instead of unpacking the whole archive to a temp directory.
+fn read_entry_cached(
+    path: &Path,
+    password: Option<&str>,
+    cache: &Mutex<LruCache<String, Vec<u8>>>,
+    filename: &str,
+) -> Result<Vec<u8>, ArchiveError> {
+    if let Some(cached) = cache.lock().unwrap().get(filename) {
+        return Ok(cached.clone());
+    }
 
-        if !extracted_path.exists() {
-            log::info!("Extracted file not found: {:?}", extracted_path);
-            return Err(ArchiveError::NoImages);
+    let data = extract_entry(path, password, filename)?;
+    cache.lock().unwrap().put(filename.to_string(), data.clone());
+    Ok(data)
+}
+
+fn extract_entry(path: &Path, password: Option<&str>, filename: &str) -> Result<Vec<u8>, ArchiveError> {
+    let mut cmd = Command::new("7z");
+    cmd.arg("e").arg("-so").arg(path).arg(filename);
+    apply_password(&mut cmd, password);
+
+    #[cfg(windows)]
+    {
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let output = cmd.output().map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            ArchiveError::MissingTool("7z".into())
+        } else {
+            ArchiveError::Io(e)
+        }
+    })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        log::info!("7z extraction failed for {:?} ({}): {}", path, filename, stderr);
+        return Err(classify_7z_failure(&stderr));
+    }
+    if output.stdout.is_empty() {
+        return Err(ArchiveError::EntryNotFound);
+    }
+    Ok(output.stdout)
+}
+
+/// Serialize `manifest` to a temp `manifest.toml` and `7z a` it into the
+/// archive in place, mirroring the RAR CLI backend's `rar u` path. `7z a`
+/// adds-or-updates the named member rather than appending a duplicate.
+fn write_manifest_sync(
+    path: &Path,
+    password: Option<&str>,
+    manifest: &Manifest,
+) -> Result<(), ArchiveError> {
+    let toml = toml::to_string_pretty(manifest)
+        .map_err(|e| ArchiveError::ManifestError(format!("Invalid TOML: {}", e)))?;
+
+    let tmp_dir = tempdir().map_err(ArchiveError::Io)?;
+    let manifest_path = tmp_dir.path().join("manifest.toml");
+    fs::write(&manifest_path, &toml).map_err(ArchiveError::Io)?;
+
+    // Archive paths can be relative, so resolve before changing directory.
+    let abs_path = path.canonicalize().map_err(ArchiveError::Io)?;
+
+    let mut cmd = Command::new("7z");
+    // Run from the temp dir and add the bare filename, the 7z equivalent of
+    // the RAR backend's `-ep1` (exclude base dir from names): otherwise 7z
+    // stores the full temp-dir path as the member name instead of updating
+    // the archive's top-level manifest.toml.
+    cmd.current_dir(tmp_dir.path())
+        .arg("a")
+        .arg(&abs_path)
+        .arg("manifest.toml");
+    apply_password(&mut cmd, password);
+
+    #[cfg(windows)]
+    {
+        cmd.creation_flags(CREATE_NO_WINDOW);
+    }
+
+    let output = cmd.output().map_err(|e| {
+        if e.kind() == std::io::ErrorKind::NotFound {
+            ArchiveError::MissingTool("7z".into())
+        } else {
+            ArchiveError::Io(e)
         }
+    })?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        log::info!("7z manifest update failed for {:?}: {}", path, stderr);
+        return Err(classify_7z_failure(&stderr));
+    }
+    Ok(())
+}
 
-        let mut file = fs::File::open(&extracted_path).map_err(|_| ArchiveError::NoImages)?;
-        let mut buffer = Vec::new();
-        file.read_to_end(&mut buffer)
-            .map_err(|_| ArchiveError::NoImages)?;
-        log::info!(
-            "Successfully read {} bytes from {:?}",
-            buffer.len(),
-            extracted_path
-        );
-        Ok(buffer)
+/// Classify a failed `7z` invocation from its stderr, instead of
+/// collapsing every failure into the same generic error.
+fn classify_7z_failure(stderr: &str) -> ArchiveError {
+    let lower = stderr.to_lowercase();
+    if lower.contains("wrong password") || lower.contains("password") {
+        ArchiveError::Encrypted
+    } else if lower.contains("crc failed") || lower.contains("data error") {
+        ArchiveError::FileCrcError
+    } else if lower.contains("unsupported method") {
+        ArchiveError::UnknownEncryption
+    } else if lower.contains("is not a supported archive") || lower.contains("cannot open") {
+        ArchiveError::ArchiveHeaderDamaged
+    } else if lower.contains("no files to process")
+        || lower.contains("cannot find the file")
+        || lower.contains("cannot open the file")
+    {
+        ArchiveError::EntryNotFound
+    } else {
+        ArchiveError::CorruptArchive(stderr.trim().to_string())
     }
 }
 
@@ -104,54 +261,23 @@ impl ImageArchiveTrait for SevenZipImageArchive {
     }
 
     async fn read_image_by_name(&mut self, filename: &str) -> Result<Vec<u8>, ArchiveError> {
-        let temp_dir_path = self.temp_dir.path().to_path_buf();
+        let path = self.path.clone();
+        let password = self.password.clone();
+        let cache = self.page_cache.clone();
         let filename = filename.to_string();
         tokio::task::spawn_blocking(move || {
-            let extracted_path = temp_dir_path.join(&filename);
-            log::info!("Reading extracted file at {:?}", extracted_path);
-
-            if !extracted_path.exists() {
-                log::info!("Extracted file not found: {:?}", extracted_path);
-                return Err(ArchiveError::NoImages);
-            }
-
-            let mut file = fs::File::open(&extracted_path).map_err(|_| ArchiveError::NoImages)?;
-            let mut buffer = Vec::new();
-            file.read_to_end(&mut buffer)
-                .map_err(|_| ArchiveError::NoImages)?;
-            log::info!(
-                "Successfully read {} bytes from {:?}",
-                buffer.len(),
-                extracted_path
-            );
-            Ok(buffer)
+            read_entry_cached(&path, password.as_deref(), &cache, &filename)
         })
         .await
         .unwrap_or_else(|e| Err(ArchiveError::Other(format!("Join error: {e}"))))
     }
 
     async fn read_manifest_string(&self) -> Result<String, ArchiveError> {
-        let temp_dir_path = self.temp_dir.path().to_path_buf();
-        let filename = "manifest.toml".to_string();
+        let path = self.path.clone();
+        let password = self.password.clone();
+        let cache = self.page_cache.clone();
         let buffer = tokio::task::spawn_blocking(move || {
-            let extracted_path = temp_dir_path.join(&filename);
-            log::info!("Reading extracted file at {:?}", extracted_path);
-
-            if !extracted_path.exists() {
-                log::info!("Extracted file not found: {:?}", extracted_path);
-                return Err(ArchiveError::NoImages);
-            }
-
-            let mut file = fs::File::open(&extracted_path).map_err(|_| ArchiveError::NoImages)?;
-            let mut buffer = Vec::new();
-            file.read_to_end(&mut buffer)
-                .map_err(|_| ArchiveError::NoImages)?;
-            log::info!(
-                "Successfully read {} bytes from {:?}",
-                buffer.len(),
-                extracted_path
-            );
-            Ok(buffer)
+            read_entry_cached(&path, password.as_deref(), &cache, "manifest.toml")
         })
         .await
         .unwrap_or_else(|e| Err(ArchiveError::Other(format!("Join error: {e}"))))?;
@@ -166,25 +292,34 @@ impl ImageArchiveTrait for SevenZipImageArchive {
         Ok(manifest)
     }
 
-    async fn write_manifest(&mut self, _manifest: &Manifest) -> Result<(), ArchiveError> {
-        // TODO: implement writing manifest with CLI
-        Ok(())
+    async fn write_manifest(&mut self, manifest: &Manifest) -> Result<(), ArchiveError> {
+        let path = self.path.clone();
+        let password = self.password.clone();
+        let manifest = manifest.clone();
+        let cache = self.page_cache.clone();
+        tokio::task::spawn_blocking(move || {
+            write_manifest_sync(&path, password.as_deref(), &manifest)?;
+            // The archive's copy changed; drop the stale cached read.
+            cache.lock().unwrap().pop("manifest.toml");
+            Ok(())
+        })
+        .await
+        .unwrap_or_else(|e| Err(ArchiveError::Other(format!("Join error: {e}"))))
     }
 }
 
 #[cfg(not(feature = "async"))]
-// #[async_trait::async_trait]
 impl ImageArchiveTrait for SevenZipImageArchive {
     fn list_images(&self) -> Vec<String> {
         self.entries.clone()
     }
 
     fn read_image_by_name(&mut self, filename: &str) -> Result<Vec<u8>, ArchiveError> {
-        self.read_file_by_name(filename)
+        self.read_file_by_name_sync(filename)
     }
 
     fn read_manifest_string(&self) -> Result<String, ArchiveError> {
-        match self.read_file_by_name("manifest.toml") {
+        match self.read_file_by_name_sync("manifest.toml") {
             Ok(buffer) => String::from_utf8(buffer).map_err(|_| {
                 ArchiveError::ManifestError("manifest.toml is not valid UTF-8".into())
             }),
@@ -204,8 +339,9 @@ impl ImageArchiveTrait for SevenZipImageArchive {
         Ok(manifest)
     }
 
-    fn write_manifest(&mut self, _manifest: &Manifest) -> Result<(), ArchiveError> {
-        // TODO: implement writing manifest with CLI
+    fn write_manifest(&mut self, manifest: &Manifest) -> Result<(), ArchiveError> {
+        write_manifest_sync(&self.path, self.password.as_deref(), manifest)?;
+        self.page_cache.lock().unwrap().pop("manifest.toml");
         Ok(())
     }
 }
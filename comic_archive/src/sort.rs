@@ -0,0 +1,56 @@
+//! Shared page-ordering helper used by every backend's `list_images`, so
+//! `page2.jpg` sorts before `page10.jpg` regardless of archive format.
+
+/// Compare two paths the way a reader expects a page sequence to sort: runs
+/// of ASCII digits compare numerically (so `page2.png` precedes
+/// `page10.png`) while everything else compares case-insensitively, so
+/// `Page1.PNG` and `page1.png` land next to each other instead of being
+/// split by case.
+pub fn natural_cmp(a: &str, b: &str) -> std::cmp::Ordering {
+    let mut a = a.chars().peekable();
+    let mut b = b.chars().peekable();
+    loop {
+        match (a.peek(), b.peek()) {
+            (None, None) => return std::cmp::Ordering::Equal,
+            (None, Some(_)) => return std::cmp::Ordering::Less,
+            (Some(_), None) => return std::cmp::Ordering::Greater,
+            (Some(ca), Some(cb)) => {
+                if ca.is_ascii_digit() && cb.is_ascii_digit() {
+                    let na = take_number(&mut a);
+                    let nb = take_number(&mut b);
+                    match na.cmp(&nb) {
+                        std::cmp::Ordering::Equal => continue,
+                        other => return other,
+                    }
+                } else {
+                    let ca = ca.to_ascii_lowercase();
+                    let cb = cb.to_ascii_lowercase();
+                    match ca.cmp(&cb) {
+                        std::cmp::Ordering::Equal => {
+                            a.next();
+                            b.next();
+                        }
+                        other => return other,
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Consume a run of ASCII digits from `chars` and parse it as a `u64`.
+/// Capped rather than arbitrary-precision since page numbers never remotely
+/// approach that range.
+fn take_number(chars: &mut std::iter::Peekable<std::str::Chars>) -> u64 {
+    let mut value: u64 = 0;
+    while let Some(c) = chars.peek() {
+        if !c.is_ascii_digit() {
+            break;
+        }
+        value = value
+            .saturating_mul(10)
+            .saturating_add(c.to_digit(10).unwrap() as u64);
+        chars.next();
+    }
+    value
+}
@@ -0,0 +1,219 @@
+use crate::error::ArchiveError;
+use crate::is_supported_format;
+use crate::prelude::*;
+
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use flate2::read::GzDecoder;
+use tar::Archive;
+
+/// Where a tar entry's image data was found during `open()`.
+///
+/// Plain tar files are backed by a seekable `File`, so we record the byte
+/// offset of the entry's data and can `seek` straight to it on a later
+/// read. Gzip-compressed tars can't be seeked into at an arbitrary byte
+/// offset, so `offset` is `None` and the read has to re-decode the stream
+/// from the start and walk headers until the name matches.
+#[derive(Clone)]
+struct TarEntry {
+    name: String,
+    offset: Option<u64>,
+    size: u64,
+}
+
+pub struct TarImageArchive {
+    path: PathBuf,
+    gzipped: bool,
+    entries: Vec<TarEntry>,
+}
+
+impl TarImageArchive {
+    pub fn new(path: &Path) -> Result<Self, ArchiveError> {
+        let gzipped = sniff_gzip(path)?;
+        let mut entries = Vec::new();
+
+        if gzipped {
+            let file = File::open(path)?;
+            let mut archive = Archive::new(GzDecoder::new(file));
+            for entry in archive.entries()? {
+                let entry = entry?;
+                let name = entry.path()?.to_string_lossy().to_string();
+                if entry.header().entry_type().is_file() && is_supported_format!(&name.to_lowercase())
+                {
+                    let size = entry.header().size()?;
+                    entries.push(TarEntry {
+                        name,
+                        offset: None,
+                        size,
+                    });
+                }
+            }
+        } else {
+            let file = File::open(path)?;
+            let mut archive = Archive::new(file);
+            for entry in archive.entries()? {
+                let entry = entry?;
+                let name = entry.path()?.to_string_lossy().to_string();
+                if entry.header().entry_type().is_file() && is_supported_format!(&name.to_lowercase())
+                {
+                    let offset = entry.raw_file_position();
+                    let size = entry.header().size()?;
+                    entries.push(TarEntry {
+                        name,
+                        offset: Some(offset),
+                        size,
+                    });
+                }
+            }
+        }
+
+        entries.sort_by(|a, b| crate::sort::natural_cmp(&a.name, &b.name));
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            gzipped,
+            entries,
+        })
+    }
+
+    /// Read `filename`'s bytes, seeking straight to its cached offset when
+    /// possible, or re-walking the (gzip) stream from the start otherwise.
+    fn read_file_by_name_sync(&self, filename: &str) -> Result<Vec<u8>, ArchiveError> {
+        let entry = self
+            .entries
+            .iter()
+            .find(|e| e.name == filename)
+            .ok_or(ArchiveError::NoImages)?;
+
+        if let Some(offset) = entry.offset {
+            let mut file = File::open(&self.path)?;
+            file.seek(SeekFrom::Start(offset))?;
+            let mut buf = vec![0u8; entry.size as usize];
+            file.read_exact(&mut buf)?;
+            return Ok(buf);
+        }
+
+        let file = File::open(&self.path)?;
+        let mut archive = Archive::new(GzDecoder::new(file));
+        for tar_entry in archive.entries()? {
+            let mut tar_entry = tar_entry?;
+            let name = tar_entry.path()?.to_string_lossy().to_string();
+            if name == filename {
+                let mut buf = Vec::new();
+                tar_entry.read_to_end(&mut buf)?;
+                return Ok(buf);
+            }
+        }
+
+        Err(ArchiveError::NoImages)
+    }
+
+    fn read_manifest_string_sync(&self) -> Result<String, ArchiveError> {
+        let data = self.read_file_by_name_sync("manifest.toml")?;
+        String::from_utf8(data)
+            .map_err(|_| ArchiveError::ManifestError("manifest.toml is not valid UTF-8".into()))
+    }
+}
+
+/// Peek at the first two bytes to tell a gzip-compressed tar from a plain
+/// one, since both commonly show up with a `.cbt` extension.
+fn sniff_gzip(path: &Path) -> Result<bool, ArchiveError> {
+    let mut file = File::open(path)?;
+    let mut magic = [0u8; 2];
+    match file.read_exact(&mut magic) {
+        Ok(()) => Ok(magic == [0x1f, 0x8b]),
+        Err(_) => Ok(false),
+    }
+}
+
+#[cfg(feature = "async")]
+#[async_trait::async_trait]
+impl ImageArchiveTrait for TarImageArchive {
+    fn list_images(&self) -> Vec<String> {
+        self.entries.iter().map(|e| e.name.clone()).collect()
+    }
+
+    fn read_image_by_name_sync(&mut self, filename: &str) -> Result<Vec<u8>, ArchiveError> {
+        self.read_file_by_name_sync(filename)
+    }
+
+    async fn read_image_by_name(&mut self, filename: &str) -> Result<Vec<u8>, ArchiveError> {
+        let path = self.path.clone();
+        let gzipped = self.gzipped;
+        let entries = self.entries.clone();
+        let filename = filename.to_string();
+        tokio::task::spawn_blocking(move || {
+            let archive = TarImageArchive {
+                path,
+                gzipped,
+                entries,
+            };
+            archive.read_file_by_name_sync(&filename)
+        })
+        .await
+        .unwrap_or_else(|e| Err(ArchiveError::Other(format!("Join error: {e}"))))
+    }
+
+    async fn read_manifest_string(&self) -> Result<String, ArchiveError> {
+        let path = self.path.clone();
+        let gzipped = self.gzipped;
+        let entries = self.entries.clone();
+        tokio::task::spawn_blocking(move || {
+            let archive = TarImageArchive {
+                path,
+                gzipped,
+                entries,
+            };
+            archive.read_manifest_string_sync()
+        })
+        .await
+        .unwrap_or_else(|e| Err(ArchiveError::Other(format!("Join error: {e}"))))
+    }
+
+    async fn read_manifest(&self) -> Result<Manifest, ArchiveError> {
+        let manifest_str = self.read_manifest_string().await?;
+        let manifest: Manifest = toml::from_str(&manifest_str)
+            .map_err(|e| ArchiveError::ManifestError(format!("Invalid TOML: {}", e)))?;
+        Ok(manifest)
+    }
+
+    async fn write_manifest(&mut self, _manifest: &Manifest) -> Result<(), ArchiveError> {
+        // Tar has no central directory to patch in place, so there's no
+        // cheap way to rewrite a single entry like the zip backend does.
+        Err(ArchiveError::RebuildRequired(
+            "the tar backend can't write archives; rebuild the archive to update its manifest"
+                .into(),
+        ))
+    }
+}
+
+#[cfg(not(feature = "async"))]
+impl ImageArchiveTrait for TarImageArchive {
+    fn list_images(&self) -> Vec<String> {
+        self.entries.iter().map(|e| e.name.clone()).collect()
+    }
+
+    fn read_image_by_name(&mut self, filename: &str) -> Result<Vec<u8>, ArchiveError> {
+        self.read_file_by_name_sync(filename)
+    }
+
+    fn read_manifest_string(&self) -> Result<String, ArchiveError> {
+        self.read_manifest_string_sync()
+    }
+
+    fn read_manifest(&self) -> Result<Manifest, ArchiveError> {
+        let manifest_str = self.read_manifest_string()?;
+        let manifest: Manifest = toml::from_str(&manifest_str)
+            .map_err(|e| ArchiveError::ManifestError(format!("Invalid TOML: {}", e)))?;
+        Ok(manifest)
+    }
+
+    fn write_manifest(&mut self, _manifest: &Manifest) -> Result<(), ArchiveError> {
+        Err(ArchiveError::RebuildRequired(
+            "the tar backend can't write archives; rebuild the archive to update its manifest"
+                .into(),
+        ))
+    }
+}
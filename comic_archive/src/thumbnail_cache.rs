@@ -0,0 +1,56 @@
+//! Disk-backed, content-addressed cache for generated thumbnails.
+//!
+//! Keyed by a SHA-256 digest of the page's raw bytes plus the requested
+//! size and JPEG quality, so identical pages across different archives
+//! share a cache entry and a page edit (which changes its bytes, and so
+//! its digest) can never return a stale thumbnail.
+//!
+//! Lands under `dirs::cache_dir()/comic_suite/thumbnails/<digest>_<size>_<quality>.jpg`,
+//! mirroring `bin_provision`'s `dirs::cache_dir()/comic_suite/bin/<key>` layout.
+
+use sha2::{Digest, Sha256};
+use std::path::PathBuf;
+
+fn cache_root() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("comic_suite")
+        .join("thumbnails")
+}
+
+/// Hex SHA-256 digest of a page's raw bytes, used as the cache key
+/// alongside the requested thumbnail size and quality.
+pub fn digest_hex(buf: &[u8]) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(buf);
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn cache_path(digest: &str, size: u32, quality: u8) -> PathBuf {
+    cache_root().join(format!("{digest}_{size}_{quality}.jpg"))
+}
+
+/// Read a cached thumbnail for `digest` at `size`/`quality`, if present.
+pub fn read(digest: &str, size: u32, quality: u8) -> Option<Vec<u8>> {
+    std::fs::read(cache_path(digest, size, quality)).ok()
+}
+
+/// Write `bytes` into the cache for `digest` at `size`/`quality`. Failures
+/// are non-fatal: the thumbnail was already generated and returned to the
+/// caller, so a write error only costs a future cache miss.
+pub fn write(digest: &str, size: u32, quality: u8, bytes: &[u8]) {
+    let path = cache_path(digest, size, quality);
+    if let Some(parent) = path.parent() {
+        if let Err(e) = std::fs::create_dir_all(parent) {
+            log::warn!("couldn't create thumbnail cache dir: {e}");
+            return;
+        }
+    }
+    if let Err(e) = std::fs::write(&path, bytes) {
+        log::warn!("couldn't write thumbnail cache entry {:?}: {e}", path);
+    }
+}
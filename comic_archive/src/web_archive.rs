@@ -1,16 +1,87 @@
+use crate::net_stats::NetStats;
 use crate::prelude::*;
+use std::path::Path;
+use std::sync::{Arc, Mutex};
 
 pub struct WebImageArchive<T> {
     pub inner: T,
     pub manifest: Manifest,
+    pub net_stats: Arc<Mutex<NetStats>>,
 }
 
 impl<T: ImageArchiveTrait> WebImageArchive<T> {
     pub fn new(inner: T, manifest: Manifest) -> Self {
-        Self { inner, manifest }
+        Self {
+            inner,
+            manifest,
+            net_stats: Arc::new(Mutex::new(NetStats::default())),
+        }
+    }
+
+    /// Download every external page and bundle them, plus an updated
+    /// manifest with `web_archive` cleared and `external_pages` removed,
+    /// into a brand-new local CBZ at `output_path` — the "make offline"
+    /// operation that turns a web archive into a self-contained comic.
+    ///
+    /// Goes through the same sync read path the viewer uses to fetch a page
+    /// (`read_image_by_name_sync` with the `async` feature, plain
+    /// `read_image_by_name` without it), so a page already present in
+    /// `web_cache` (from browsing the archive earlier) is a disk read
+    /// rather than a re-download. Runs entirely on the calling thread;
+    /// callers on an async runtime should run this inside
+    /// `tokio::task::spawn_blocking` the same way
+    /// `cache::image_cache::load_image_async` does for archive reads.
+    pub fn materialize_offline(&mut self, output_path: &Path) -> Result<(), ArchiveError> {
+        use std::io::Write;
+
+        let urls = self.list_images();
+        if urls.is_empty() {
+            return Err(ArchiveError::ManifestError(
+                "No external pages to download".into(),
+            ));
+        }
+
+        let file = std::fs::File::create(output_path)?;
+        let mut zip = zip::ZipWriter::new(file);
+        let options = zip::write::FileOptions::default()
+            .compression_method(zip::CompressionMethod::Deflated)
+            .unix_permissions(0o644);
+
+        for (index, url) in urls.iter().enumerate() {
+            #[cfg(feature = "async")]
+            let bytes = self.read_image_by_name_sync(url)?;
+            #[cfg(not(feature = "async"))]
+            let bytes = self.read_image_by_name(url)?;
+
+            zip.start_file(page_file_name(index, url), options)?;
+            zip.write_all(&bytes)?;
+        }
+
+        let mut manifest = self.manifest.clone();
+        manifest.meta.web_archive = false;
+        manifest.external_pages = None;
+        let manifest_str = toml::to_string_pretty(&manifest)
+            .map_err(|e| ArchiveError::ManifestError(format!("Couldn't serialize: {}", e)))?;
+        zip.start_file("manifest.toml", options)?;
+        zip.write_all(manifest_str.as_bytes())?;
+
+        zip.finish()?;
+        Ok(())
     }
 }
 
+/// Pick a stable in-archive filename for page `index`, reusing `url`'s
+/// extension (so decoders still dispatch by format) when it looks like a
+/// real image extension, falling back to `.jpg` otherwise.
+fn page_file_name(index: usize, url: &str) -> String {
+    let ext = url
+        .rsplit('.')
+        .next()
+        .filter(|e| e.len() <= 4 && !e.is_empty() && e.chars().all(|c| c.is_ascii_alphanumeric()))
+        .unwrap_or("jpg");
+    format!("page_{:04}.{}", index + 1, ext)
+}
+
 #[cfg(feature = "async")]
 #[async_trait::async_trait]
 impl<T: ImageArchiveTrait + Send + Sync> ImageArchiveTrait for WebImageArchive<T> {
@@ -23,6 +94,16 @@ impl<T: ImageArchiveTrait + Send + Sync> ImageArchiveTrait for WebImageArchive<T
     }
 
     fn read_image_by_name_sync(&mut self, filename: &str) -> Result<Vec<u8>, ArchiveError> {
+        let cache_key = crate::web_cache::key(filename);
+        if let Some(cached) = crate::web_cache::read(&cache_key) {
+            let len = cached.len() as u64;
+            self.net_stats
+                .lock()
+                .unwrap()
+                .record_fetch(filename, len, true);
+            return Ok(cached);
+        }
+
         let resp = reqwest::blocking::get(filename).map_err(|e| {
             ArchiveError::NetworkError(format!("Failed to GET {}: {}", filename, e))
         })?;
@@ -39,10 +120,25 @@ impl<T: ImageArchiveTrait + Send + Sync> ImageArchiveTrait for WebImageArchive<T
             ArchiveError::NetworkError(format!("Failed to read bytes from {}: {}", filename, e))
         })?;
 
+        self.net_stats
+            .lock()
+            .unwrap()
+            .record_fetch(filename, bytes.len() as u64, false);
+        crate::web_cache::write(&cache_key, &bytes);
         Ok(bytes.to_vec())
     }
 
     async fn read_image_by_name(&mut self, filename: &str) -> Result<Vec<u8>, ArchiveError> {
+        let cache_key = crate::web_cache::key(filename);
+        if let Some(cached) = crate::web_cache::read(&cache_key) {
+            let len = cached.len() as u64;
+            self.net_stats
+                .lock()
+                .unwrap()
+                .record_fetch(filename, len, true);
+            return Ok(cached);
+        }
+
         let resp = reqwest::get(filename).await.map_err(|e| {
             ArchiveError::NetworkError(format!("Failed to GET {}: {}", filename, e))
         })?;
@@ -59,6 +155,11 @@ impl<T: ImageArchiveTrait + Send + Sync> ImageArchiveTrait for WebImageArchive<T
             ArchiveError::NetworkError(format!("Failed to read bytes from {}: {}", filename, e))
         })?;
 
+        self.net_stats
+            .lock()
+            .unwrap()
+            .record_fetch(filename, bytes.len() as u64, false);
+        crate::web_cache::write(&cache_key, &bytes);
         Ok(bytes.to_vec())
     }
 
@@ -76,6 +177,14 @@ impl<T: ImageArchiveTrait + Send + Sync> ImageArchiveTrait for WebImageArchive<T
     async fn write_manifest(&mut self, manifest: &Manifest) -> Result<(), ArchiveError> {
         self.inner.write_manifest(manifest).await
     }
+
+    fn net_stats(&self) -> Option<Arc<Mutex<NetStats>>> {
+        Some(self.net_stats.clone())
+    }
+
+    fn materialize_offline(&mut self, output_path: &Path) -> Result<(), ArchiveError> {
+        self.materialize_offline(output_path)
+    }
 }
 
 #[cfg(not(feature = "async"))]
@@ -89,6 +198,16 @@ impl<T: ImageArchiveTrait> ImageArchiveTrait for WebImageArchive<T> {
     }
 
     fn read_image_by_name(&mut self, filename: &str) -> Result<Vec<u8>, ArchiveError> {
+        let cache_key = crate::web_cache::key(filename);
+        if let Some(cached) = crate::web_cache::read(&cache_key) {
+            let len = cached.len() as u64;
+            self.net_stats
+                .lock()
+                .unwrap()
+                .record_fetch(filename, len, true);
+            return Ok(cached);
+        }
+
         let resp = reqwest::blocking::get(filename).map_err(|e| {
             ArchiveError::NetworkError(format!("Failed to GET {}: {}", filename, e))
         })?;
@@ -105,6 +224,11 @@ impl<T: ImageArchiveTrait> ImageArchiveTrait for WebImageArchive<T> {
             ArchiveError::NetworkError(format!("Failed to read bytes from {}: {}", filename, e))
         })?;
 
+        self.net_stats
+            .lock()
+            .unwrap()
+            .record_fetch(filename, bytes.len() as u64, false);
+        crate::web_cache::write(&cache_key, &bytes);
         Ok(bytes.to_vec())
     }
 
@@ -121,4 +245,12 @@ impl<T: ImageArchiveTrait> ImageArchiveTrait for WebImageArchive<T> {
     fn write_manifest(&mut self, manifest: &Manifest) -> Result<(), ArchiveError> {
         self.inner.write_manifest(manifest)
     }
+
+    fn net_stats(&self) -> Option<Arc<Mutex<NetStats>>> {
+        Some(self.net_stats.clone())
+    }
+
+    fn materialize_offline(&mut self, output_path: &Path) -> Result<(), ArchiveError> {
+        self.materialize_offline(output_path)
+    }
 }
@@ -0,0 +1,105 @@
+//! Disk-backed cache of downloaded `WebImageArchive` page bodies, keyed by a
+//! SHA-256 digest of the source URL, so re-opening a web manifest serves
+//! already-fetched pages from disk instead of re-downloading them (and, once
+//! every page has been fetched once via `download_all`, lets the archive be
+//! read with no network connection at all).
+//!
+//! Entries are flat `<digest>` files holding the raw response body exactly
+//! as downloaded. `enforce_budget` runs after every write and deletes the
+//! oldest-by-mtime entries until the directory is back under
+//! `WEB_CACHE_BUDGET_BYTES`, mirroring `page_cache`'s layout.
+
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Total on-disk size this cache is allowed to grow to before
+/// `enforce_budget` starts evicting the oldest entries.
+const WEB_CACHE_BUDGET_BYTES: u64 = 512 * 1024 * 1024;
+
+fn cache_root() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("comic_suite")
+        .join("web")
+}
+
+/// Cache key for `url`.
+pub fn key(url: &str) -> String {
+    let mut hasher = Sha256::new();
+    hasher.update(url.as_bytes());
+    hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+fn cache_path(key: &str) -> PathBuf {
+    cache_root().join(key)
+}
+
+/// Read a cached page body back, if present.
+pub fn read(key: &str) -> Option<Vec<u8>> {
+    std::fs::read(cache_path(key)).ok()
+}
+
+/// Write a downloaded page body into the cache, then enforce the size
+/// budget. Failures are non-fatal: the body was already returned to the
+/// caller, so a write error only costs a future cache miss.
+pub fn write(key: &str, body: &[u8]) {
+    let path = cache_path(key);
+    let Some(parent) = path.parent() else {
+        return;
+    };
+    if let Err(e) = std::fs::create_dir_all(parent) {
+        log::warn!("couldn't create web cache dir: {e}");
+        return;
+    }
+    if let Err(e) = std::fs::write(&path, body) {
+        log::warn!("couldn't write web cache entry {:?}: {e}", path);
+        return;
+    }
+    enforce_budget();
+}
+
+/// Delete the oldest-by-mtime entries until the cache directory's total
+/// size is back under `WEB_CACHE_BUDGET_BYTES`.
+fn enforce_budget() {
+    let Ok(entries) = std::fs::read_dir(cache_root()) else {
+        return;
+    };
+    let mut files: Vec<(PathBuf, u64, SystemTime)> = entries
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let meta = e.metadata().ok()?;
+            let modified = meta.modified().ok()?;
+            Some((e.path(), meta.len(), modified))
+        })
+        .collect();
+
+    let total: u64 = files.iter().map(|(_, len, _)| len).sum();
+    if total <= WEB_CACHE_BUDGET_BYTES {
+        return;
+    }
+
+    let mut remaining = total;
+    files.sort_by_key(|(_, _, modified)| *modified);
+    for (path, len, _) in files {
+        if remaining <= WEB_CACHE_BUDGET_BYTES {
+            break;
+        }
+        if std::fs::remove_file(&path).is_ok() {
+            remaining = remaining.saturating_sub(len);
+        }
+    }
+}
+
+fn path_for(url: &str) -> PathBuf {
+    cache_path(&key(url))
+}
+
+/// Whether `url` has already been fetched and cached on disk.
+pub fn has(url: &str) -> bool {
+    path_for(url).is_file()
+}
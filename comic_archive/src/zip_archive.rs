@@ -77,7 +77,7 @@ impl ImageArchiveTrait for ZipImageArchive {
                 }
             }
         }
-        images.sort();
+        images.sort_by(|a, b| crate::sort::natural_cmp(a, b));
         images
     }
 
@@ -197,7 +197,7 @@ impl ImageArchiveTrait for ZipImageArchive {
                 }
             }
         }
-        images.sort();
+        images.sort_by(|a, b| crate::sort::natural_cmp(a, b));
         images
     }
 
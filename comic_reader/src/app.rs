@@ -7,6 +7,15 @@ use crate::{
 use egui::{Pos2, epaint::tessellator::Path};
 use tokio::sync::Semaphore;
 
+/// Which way the reader last turned pages, used by `preload_images` to bias
+/// its prefetch window so reversing direction doesn't start cold.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum PageDirection {
+    #[default]
+    Forward,
+    Backward,
+}
+
 /// The main application struct, holding all state.
 pub struct CBZViewerApp {
     pub archive_path: Option<PathBuf>,
@@ -22,8 +31,25 @@ pub struct CBZViewerApp {
     pub drag_start: Option<egui::Pos2>,
     pub double_page_mode: bool,
     pub right_to_left: bool,
+    /// Width/height ratio above which a page is treated as a wide spread
+    /// scan and rendered solo in dual-page mode. See `draw_dual_page`.
+    pub spread_aspect_threshold: f32,
     pub has_initialised_zoom: bool,
     pub loading_pages: Arc<Mutex<HashSet<usize>>>,
+    /// Pages whose most recent load attempt failed, keyed by page index with
+    /// the error text to show in place of the page. Cleared on the next
+    /// successful load of that page; see `load_image_async`.
+    pub page_errors: Arc<Mutex<std::collections::HashMap<usize, String>>>,
+    /// Bounded pool of threads decoding `preload_images`' prefetch window.
+    /// Replaces one `tokio::task::spawn` per preloaded page: jobs for pages
+    /// that scroll out of the window, or whose document is backgrounded,
+    /// are dropped by generation instead of aborted (see
+    /// `PageDecodeWorkerPool::bump_generation`).
+    pub page_decode_pool: PageDecodeWorkerPool,
+    /// Updated by `goto_page` whenever the current page moves, and read by
+    /// `preload_images` to weight its window toward the way the reader is
+    /// actually heading rather than always reading forward.
+    pub page_direction: PageDirection,
     pub page_goto_box: String,
     pub show_manifest_editor: bool,
     pub on_goto_page: bool,
@@ -31,27 +57,127 @@ pub struct CBZViewerApp {
     pub on_open_comic: bool,
     pub on_open_folder: bool,
     pub on_save_image: bool,
+    /// In-app library browser (thumbnail grid over a chosen folder of
+    /// comics), an alternative to opening archives one at a time through
+    /// the file browser or a native dialog.
+    pub library: LibraryState,
+    pub on_open_library: bool,
+    /// Runtime-adjustable display/caching settings, loaded from (and saved
+    /// back to) disk; see `ui::settings_modal`.
+    pub settings: Settings,
+    pub show_settings_modal: bool,
     pub is_web_archive: bool,
     pub total_pages: usize,
     pub show_thumbnail_grid: bool,
-    pub thumbnail_cache: Arc<Mutex<std::collections::HashMap<usize, image::DynamicImage>>>,
+    /// How `display_thumbnail_grid` fits pages into their square cells.
+    pub thumb_fit: ThumbFit,
+    /// Keyboard-navigable cursor within `display_thumbnail_grid`, moved by
+    /// arrow/Home/End/PageUp/PageDown and opened with Enter/Space.
+    pub selected_thumb: usize,
+    pub thumbnail_cache: Arc<Mutex<std::collections::HashMap<usize, ThumbImage>>>,
+    /// Uploaded textures for `thumbnail_cache`'s entries, so the grid
+    /// re-uploads a thumbnail at most once instead of every frame.
+    pub thumbnail_textures: ThumbnailTextureCache,
     pub thumb_semaphore: Arc<Semaphore>,
+    /// `egui::ScrollArea` vertical offset the thumbnail grid had last frame,
+    /// used to derive `thumb_grid_scroll_velocity`.
+    pub thumb_grid_scroll_offset: f32,
+    /// Signed scroll delta (pixels/frame) from the previous frame, used to
+    /// bias the prefetch ring toward the direction of travel.
+    pub thumb_grid_scroll_velocity: f32,
     pub new_page: Option<PathBuf>,
     pub show_debug_menu: bool,
+    /// Toggles the scrollable log history panel (`display_log_history`),
+    /// separate from the transient toast shown by `display_notification_bar`.
+    pub show_log_history: bool,
+    /// Which levels `display_log_history` shows.
+    pub log_filter_info: bool,
+    pub log_filter_warning: bool,
+    pub log_filter_error: bool,
+    /// Runtime mirror of `comic_archive::decode`'s turbojpeg on/off switch
+    /// (only meaningful with the `turbo` feature compiled in); lets a user
+    /// fall back to the pure-Rust JPEG decoder without a rebuild.
+    #[cfg(feature = "turbo")]
+    pub use_turbo_jpeg: bool,
+    /// Ring buffer of every `log` record (TRACE..ERROR), installed by
+    /// `ui::log_console::init`. Rendered by the Debug Info window's log
+    /// console section.
+    pub log_console: crate::ui::log_console::LogBuffer,
+    /// Minimum level `display_debug_menu`'s log console shows.
+    pub log_console_level: log::LevelFilter,
+    /// Substring filter for the log console; empty shows everything.
+    pub log_console_search: String,
     pub slideshow_mode: bool,
     pub slideshow_last_tick: std::time::Instant,
     pub slideshow_interval_secs: f32,
     pub show_slideshow_interval_popup: bool, // New field to control the popup
     pub archive_view: ArchiveView,
+    /// Watches `archive_path` for external changes and triggers a reload.
+    pub watcher: Option<crate::watch::ArchiveWatcher>,
+    /// Continuous vertical "webtoon" scroll mode, as an alternative to single/dual page.
+    pub continuous_scroll_mode: bool,
+    /// Scroll position within the continuous-mode virtual canvas, in content pixels.
+    pub scroll_offset: f32,
+    /// Per-page heights (scaled to panel width) for the continuous-mode layout.
+    /// Entries are estimates until the page is decoded, then reflowed to the real value.
+    /// Indexed by page number rather than a windowed map: `draw_continuous_scroll`
+    /// only needs a prefix-sum pass over this `Vec` to find the visible range, and
+    /// textures for pages outside it are evicted from `texture_cache` every frame
+    /// (see `TextureCache::evict_outside`), so the window is already bounded there.
+    pub page_heights: Vec<f32>,
+    /// Other open comics. The currently-active comic's state lives in the
+    /// flat fields above; switching tabs swaps them with an entry here.
+    pub tabs: Vec<ComicTab>,
+    /// Index into `tabs` of the comic whose state is currently loaded into
+    /// the flat fields above.
+    pub active_tab: usize,
+    /// Bookmarks for the currently open archive, loaded from (and saved back
+    /// to) the on-disk bookmark store.
+    pub bookmarks: Vec<Bookmark>,
+    pub show_bookmarks_popup: bool,
+    /// Set by `load_new_file` when the archive has a saved "last read" page
+    /// beyond the first; shows a one-shot "resume reading?" prompt instead
+    /// of silently jumping there.
+    pub resume_prompt: Option<usize>,
+    /// Whether `load_new_file` starts a `watcher` for the opened archive.
+    /// Off by default for archives on slow/remote storage, where polling
+    /// the filesystem for every read can itself be the bottleneck.
+    pub watch_enabled: bool,
+    /// Page index whose `Metadata.comments` caption the reader has dismissed
+    /// via `draw_page_comment`'s close button, so it doesn't pop back up
+    /// every frame until the page changes.
+    pub dismissed_comment_page: Option<usize>,
+    /// Set when opening `new_page` fails with `AppError::Encrypted`, so the
+    /// password prompt dialog knows which file to retry.
+    pub pending_password_prompt: Option<PathBuf>,
+    /// Text entered into the password prompt dialog.
+    pub password_input: String,
+    /// Recently opened archives and the last file-dialog directory,
+    /// persisted across restarts. Unlike most fields above, this survives
+    /// `load_new_file_from_archive`'s reset rather than being tied to the
+    /// active tab.
+    pub recents: RecentsStore,
+    /// Toggles the in-app directory browser (`display_file_browser`), an
+    /// alternative to the native file dialog.
+    pub show_file_browser: bool,
+    /// Directory currently listed in the file browser. Restored from the
+    /// most recently visited folder (see `ui::file_browser`) on launch.
+    pub file_browser_dir: PathBuf,
+    pub show_recents_panel: bool,
+    /// Textures for `display_recents_panel`'s thumbnails, loaded lazily
+    /// from `comic_archive::thumbnail_cache` and kept for the app's
+    /// lifetime since the list is small.
+    pub recents_textures: std::collections::HashMap<PathBuf, TextureHandle>,
 }
 
 impl Default for CBZViewerApp {
     fn default() -> Self {
+        let settings = Settings::load();
         Self {
             archive_path: None,
             archive: None,
             filenames: None,
-            image_lru: new_image_cache(CACHE_SIZE),
+            image_lru: new_image_cache(settings.cache_memory_budget_bytes),
             current_page: 0,
             texture_cache: TextureCache::new(),
             ui_logger: Arc::new(Mutex::new(UiLogger::new())),
@@ -59,10 +185,12 @@ impl Default for CBZViewerApp {
             pan_offset: Vec2::ZERO,
             original_pan_offset: Vec2::ZERO,
             drag_start: None,
-            double_page_mode: DEFAULT_DUAL_PAGE_MODE,
-            right_to_left: DEFAULT_RIGHT_TO_LEFT,
+            double_page_mode: settings.dual_page_mode,
+            right_to_left: settings.right_to_left,
+            spread_aspect_threshold: DEFAULT_SPREAD_ASPECT_THRESHOLD,
             has_initialised_zoom: false,
             loading_pages: Arc::new(Mutex::new(HashSet::new())),
+            page_errors: Arc::new(Mutex::new(std::collections::HashMap::new())),
             page_goto_box: "1".to_string(),
             show_manifest_editor: false,
             on_goto_page: false,
@@ -70,18 +198,60 @@ impl Default for CBZViewerApp {
             on_open_comic: false,
             on_open_folder: false,
             on_save_image: false,
+            library: LibraryState::default(),
+            on_open_library: false,
+            settings,
+            show_settings_modal: false,
             is_web_archive: false,
             total_pages: 0,
             show_thumbnail_grid: false,
+            thumb_fit: DEFAULT_THUMB_FIT,
+            selected_thumb: 0,
             thumbnail_cache: Arc::new(Mutex::new(std::collections::HashMap::new())),
+            thumbnail_textures: ThumbnailTextureCache::new(),
             thumb_semaphore: Arc::new(Semaphore::new(8)), // Limit to 8 concurrent thumbnail loads
+            thumb_grid_scroll_offset: 0.0,
+            thumb_grid_scroll_velocity: 0.0,
             new_page: None,
             show_debug_menu: false,
+            show_log_history: false,
+            log_filter_info: true,
+            log_filter_warning: true,
+            log_filter_error: true,
+            #[cfg(feature = "turbo")]
+            use_turbo_jpeg: true,
+            log_console: crate::ui::log_console::buffer(),
+            log_console_level: log::LevelFilter::Debug,
+            log_console_search: String::new(),
             slideshow_mode: false,
             slideshow_last_tick: std::time::Instant::now(),
             slideshow_interval_secs: 5.0, // Default slideshow interval
             show_slideshow_interval_popup: false, // Initialize the popup control field
             archive_view: ArchiveView::default(),
+            watcher: None,
+            continuous_scroll_mode: false,
+            scroll_offset: 0.0,
+            page_heights: Vec::new(),
+            page_decode_pool: PageDecodeWorkerPool::new(),
+            page_direction: PageDirection::default(),
+            tabs: Vec::new(),
+            active_tab: 0,
+            bookmarks: Vec::new(),
+            show_bookmarks_popup: false,
+            resume_prompt: None,
+            watch_enabled: true,
+            dismissed_comment_page: None,
+            pending_password_prompt: None,
+            password_input: String::new(),
+            recents: RecentsStore::load(),
+            show_recents_panel: false,
+            recents_textures: std::collections::HashMap::new(),
+            show_file_browser: false,
+            file_browser_dir: crate::ui::file_browser::load_recent_dirs()
+                .into_iter()
+                .next()
+                .or_else(dirs::home_dir)
+                .unwrap_or_else(|| PathBuf::from(".")),
         }
     }
 }
@@ -103,6 +273,23 @@ impl CBZViewerApp {
         Ok(app)
     }
 
+    /// The max source dimension to decode the actively displayed page(s)
+    /// at, derived from the viewport size and device pixel ratio. `None`
+    /// (decode at full resolution) once the user has zoomed in past 100%,
+    /// since a downsampled decode can't supply the extra detail zooming in
+    /// past native size is meant to reveal; floored at
+    /// `MAX_PRELOAD_DIMENSION` so the active page is never decoded at a
+    /// lower resolution than the read-ahead pages around it.
+    fn active_page_max_dimension(&self, ctx: &egui::Context) -> Option<u32> {
+        if self.zoom > 1.0 {
+            return None;
+        }
+        let screen = ctx.screen_rect().size();
+        let ppp = ctx.pixels_per_point();
+        let target = (screen.x.max(screen.y) * ppp * ACTIVE_PAGE_OVERSAMPLE_FACTOR).ceil() as u32;
+        Some(target.max(MAX_PRELOAD_DIMENSION))
+    }
+
     pub fn reset_zoom(&mut self, area: Rect, loaded: &LoadedPage) {
         let (w, h) = loaded.image.dimensions();
         let avail = area.size();
@@ -113,6 +300,44 @@ impl CBZViewerApp {
         self.has_initialised_zoom = true;
     }
 
+    /// Combined dimensions of whatever is currently on screen (both pages in
+    /// double-page mode, just the current page otherwise), for callers like
+    /// `clamp_pan` that need the on-screen image size outside of
+    /// `display_central_image_area`'s own loading/drawing pass.
+    pub fn current_page_dims(&self) -> Option<(u32, u32)> {
+        let mut image_lru = self.image_lru.lock().unwrap();
+        let loaded1 = image_lru.get(&self.current_page).cloned();
+        if self.double_page_mode {
+            let page2 = self.current_page + 1;
+            if let (Some(l1), Some(l2)) = (&loaded1, image_lru.get(&page2)) {
+                let (w1, h1) = l1.image.dimensions();
+                let (w2, h2) = l2.image.dimensions();
+                return Some((w1 + w2, h1.max(h2)));
+            }
+        }
+        loaded1.map(|l| l.image.dimensions())
+    }
+
+    /// How many pages `goto_next_page`/`goto_prev_page` should step by
+    /// starting at `page`: 1 outside dual-page mode, or when `page` (or its
+    /// would-be partner) is a wide spread that `draw_dual_page` renders
+    /// solo; 2 for an ordinary pair. Falls back to the non-spread step when
+    /// the relevant pages aren't decoded yet (e.g. far outside the preload
+    /// window), since dimensions aren't known until then.
+    fn dual_page_step(&self, page: usize) -> usize {
+        if !self.double_page_mode {
+            return 1;
+        }
+        let image_lru = self.image_lru.lock().unwrap();
+        let left_is_spread = image_lru
+            .get(&page)
+            .is_some_and(|l| is_spread_page(l, self.spread_aspect_threshold));
+        let right_is_spread = image_lru
+            .get(&(page + 1))
+            .is_some_and(|r| is_spread_page(r, self.spread_aspect_threshold));
+        if left_is_spread || right_is_spread { 1 } else { 2 }
+    }
+
     /// Go to the previous page (with bounds checking).
     pub fn goto_prev_page(&mut self) {
         if self.current_page == 0 {
@@ -121,14 +346,14 @@ impl CBZViewerApp {
             }
             return;
         }
-        let step = if self.double_page_mode { 2 } else { 1 };
+        let step = self.dual_page_step(self.current_page.saturating_sub(2));
         let new_page = self.current_page.saturating_sub(step);
         self.goto_page(new_page);
     }
 
     /// Go to the next page (with bounds checking).
     pub fn goto_next_page(&mut self) {
-        let step = if self.double_page_mode { 2 } else { 1 };
+        let step = self.dual_page_step(self.current_page);
         let new_page = self.current_page + step;
         self.goto_page(new_page);
     }
@@ -146,7 +371,13 @@ impl CBZViewerApp {
                 }
                 return false;
             }
+            self.page_direction = if page < self.current_page {
+                PageDirection::Backward
+            } else {
+                PageDirection::Forward
+            };
             self.current_page = page;
+            self.record_last_read();
             true
         } else {
             if let Ok(mut logger) = self.ui_logger.lock() {
@@ -156,14 +387,78 @@ impl CBZViewerApp {
         }
     }
 
+    /// Toggle a bookmark on `page`, persisting the change to the on-disk
+    /// bookmark store.
+    pub fn toggle_bookmark(&mut self, page: usize) {
+        if let Some(pos) = self.bookmarks.iter().position(|b| b.page == page) {
+            self.bookmarks.remove(pos);
+        } else {
+            self.bookmarks.push(Bookmark {
+                page,
+                label: format!("Page {}", page + 1),
+            });
+            self.bookmarks.sort_by_key(|b| b.page);
+        }
+        self.save_bookmarks();
+    }
+
+    fn save_bookmarks(&mut self) {
+        if let Some(path) = self.archive_path.clone() {
+            let mut store = BookmarkStore::load();
+            let mut entry = store.for_archive(&path);
+            entry.marks = self.bookmarks.clone();
+            store.set_for_archive(&path, entry);
+        }
+    }
+
+    /// Persist `current_page` as the archive's "last read" position, so
+    /// reopening the comic resumes where the reader left off.
+    fn record_last_read(&mut self) {
+        if let Some(path) = self.archive_path.clone() {
+            let mut store = BookmarkStore::load();
+            let mut entry = store.for_archive(&path);
+            entry.last_read = Some(self.current_page);
+            store.set_for_archive(&path, entry);
+        }
+    }
+
+    /// Open `path` in a *new* tab, leaving any already-open comics alone.
     pub async fn load_new_file(&mut self, path: PathBuf) -> Result<(), AppError> {
-        // Reset self to default values, but keep the logger and context if needed
+        let archive = ImageArchive::process(&path).await?;
+        self.load_new_file_from_archive(path, archive)
+    }
+
+    /// Same as `load_new_file`, but for a password-protected RAR/7z archive.
+    /// Kept separate rather than adding an `Option<&str>` parameter to
+    /// `load_new_file` so the common unencrypted path doesn't thread a
+    /// password argument through every call site.
+    pub async fn load_new_file_with_password(
+        &mut self,
+        path: PathBuf,
+        password: &str,
+    ) -> Result<(), AppError> {
+        let archive = ImageArchive::process_with_password(&path, Some(password)).await?;
+        self.load_new_file_from_archive(path, archive)
+    }
+
+    /// Shared tail of `load_new_file`/`load_new_file_with_password`: swap in
+    /// a freshly opened archive's state, stashing whatever was active as its
+    /// own tab.
+    fn load_new_file_from_archive(
+        &mut self,
+        path: PathBuf,
+        archive: ImageArchive,
+    ) -> Result<(), AppError> {
+        // Reset the flat (active-tab) fields to default values, but keep the
+        // logger and context if needed.
         let mut new_self = Self::default();
 
         // Optionally preserve logger or other fields if needed
         new_self.ui_logger = Arc::clone(&self.ui_logger);
-
-        let archive = ImageArchive::process(&path).await?;
+        new_self.watch_enabled = self.watch_enabled;
+        new_self.recents = self.recents.clone();
+        new_self.show_recents_panel = self.show_recents_panel;
+        new_self.recents_textures = std::mem::take(&mut self.recents_textures);
 
         let archive = Arc::new(Mutex::new(archive));
         if let Ok(guard) = archive.lock() {
@@ -171,23 +466,249 @@ impl CBZViewerApp {
             new_self.filenames = Some(filenames);
             new_self.is_web_archive = guard.manifest.meta.web_archive;
         }
-        new_self.archive_path = Some(path);
+        new_self.recents.record_opened(&path);
+        if let Some(first_filename) = new_self.filenames.as_ref().and_then(|f| f.first().cloned()) {
+            let archive_for_thumb = Arc::clone(&archive);
+            let path_for_thumb = path.clone();
+            tokio::spawn(async move {
+                let raw = {
+                    let mut guard = archive_for_thumb.lock().unwrap();
+                    guard.backend.read_image_by_name_sync(&first_filename)
+                };
+                let Ok(raw) = raw else { return };
+                let digest = comic_archive::thumbnail_cache::digest_hex(&raw);
+
+                let generated = {
+                    let mut guard = archive_for_thumb.lock().unwrap();
+                    futures::executor::block_on(guard.generate_thumbnail(
+                        &first_filename,
+                        RECENT_THUMB_SIZE,
+                        RECENT_THUMB_QUALITY,
+                    ))
+                };
+                if generated.is_ok() {
+                    let mut store = RecentsStore::load();
+                    store.set_thumb_digest(&path_for_thumb, digest);
+                }
+            });
+        }
+        new_self.watcher = if new_self.watch_enabled {
+            crate::watch::ArchiveWatcher::new(&path)
+        } else {
+            None
+        };
         new_self.total_pages = new_self.filenames.as_ref().map_or(0, |f| f.len());
         new_self.archive = Some(Arc::clone(&archive));
-        new_self.image_lru = new_image_cache(CACHE_SIZE);
-        new_self.current_page = 0;
+        new_self.image_lru = new_image_cache(CACHE_MEMORY_BUDGET_BYTES);
+
+        let saved = BookmarkStore::load().for_archive(&path);
+        new_self.bookmarks = saved.marks;
+        // Rather than silently jumping to the last-read page, offer it via
+        // `resume_prompt` and let the reader decide; start from the first
+        // page until they do.
+        new_self.resume_prompt = saved
+            .last_read
+            .map(|page| page.min(new_self.total_pages.saturating_sub(1)))
+            .filter(|&page| page > 0);
+        new_self.archive_path = Some(path);
+
+        // Stash the currently active comic (if any) as its own tab, then
+        // open the new comic in a fresh tab rather than replacing it.
+        if self.archive.is_some() {
+            let outgoing = self.take_active_as_tab();
+            if self.active_tab < self.tabs.len() {
+                self.tabs[self.active_tab] = outgoing;
+            } else {
+                self.tabs.push(outgoing);
+            }
+        }
+        self.tabs.push(ComicTab::default());
+        self.active_tab = self.tabs.len() - 1;
+        let tabs = std::mem::take(&mut self.tabs);
+        let active_tab = self.active_tab;
 
         // Move new_self's fields into self
         *self = new_self;
+        self.tabs = tabs;
+        self.active_tab = active_tab;
 
         Ok(())
     }
 
-    /// Called whenever the page changes: resets zoom, pan, and clears texture cache.
+    /// Move the active comic's state out of the flat fields into a `ComicTab`,
+    /// leaving defaults behind. Used when stashing or closing the active tab.
+    fn take_active_as_tab(&mut self) -> ComicTab {
+        // A backgrounded tab has nothing on screen to prefetch for; drop its
+        // in-flight decodes rather than carrying them along.
+        self.page_decode_pool.bump_generation();
+        ComicTab {
+            archive_path: self.archive_path.take(),
+            archive: self.archive.take(),
+            filenames: self.filenames.take(),
+            image_lru: std::mem::replace(&mut self.image_lru, new_image_cache(CACHE_MEMORY_BUDGET_BYTES)),
+            current_page: self.current_page,
+            texture_cache: std::mem::replace(&mut self.texture_cache, TextureCache::new()),
+            zoom: self.zoom,
+            pan_offset: self.pan_offset,
+            original_pan_offset: self.original_pan_offset,
+            drag_start: self.drag_start.take(),
+            double_page_mode: self.double_page_mode,
+            right_to_left: self.right_to_left,
+            spread_aspect_threshold: self.spread_aspect_threshold,
+            has_initialised_zoom: self.has_initialised_zoom,
+            loading_pages: std::mem::replace(
+                &mut self.loading_pages,
+                Arc::new(Mutex::new(HashSet::new())),
+            ),
+            page_errors: std::mem::replace(
+                &mut self.page_errors,
+                Arc::new(Mutex::new(std::collections::HashMap::new())),
+            ),
+            is_web_archive: self.is_web_archive,
+            total_pages: self.total_pages,
+            thumbnail_cache: std::mem::replace(
+                &mut self.thumbnail_cache,
+                Arc::new(Mutex::new(std::collections::HashMap::new())),
+            ),
+            thumbnail_textures: std::mem::replace(
+                &mut self.thumbnail_textures,
+                ThumbnailTextureCache::new(),
+            ),
+            watcher: self.watcher.take(),
+            continuous_scroll_mode: self.continuous_scroll_mode,
+            scroll_offset: self.scroll_offset,
+            page_heights: std::mem::take(&mut self.page_heights),
+            bookmarks: std::mem::take(&mut self.bookmarks),
+            show_bookmarks_popup: self.show_bookmarks_popup,
+        }
+    }
+
+    /// Load a `ComicTab`'s state into the flat (active-tab) fields.
+    fn apply_tab(&mut self, tab: ComicTab) {
+        self.archive_path = tab.archive_path;
+        self.archive = tab.archive;
+        self.filenames = tab.filenames;
+        self.image_lru = tab.image_lru;
+        self.current_page = tab.current_page;
+        self.texture_cache = tab.texture_cache;
+        self.zoom = tab.zoom;
+        self.pan_offset = tab.pan_offset;
+        self.original_pan_offset = tab.original_pan_offset;
+        self.drag_start = tab.drag_start;
+        self.double_page_mode = tab.double_page_mode;
+        self.right_to_left = tab.right_to_left;
+        self.spread_aspect_threshold = tab.spread_aspect_threshold;
+        self.has_initialised_zoom = tab.has_initialised_zoom;
+        self.loading_pages = tab.loading_pages;
+        self.page_errors = tab.page_errors;
+        self.is_web_archive = tab.is_web_archive;
+        self.total_pages = tab.total_pages;
+        self.thumbnail_cache = tab.thumbnail_cache;
+        self.thumbnail_textures = tab.thumbnail_textures;
+        self.watcher = tab.watcher;
+        self.continuous_scroll_mode = tab.continuous_scroll_mode;
+        self.scroll_offset = tab.scroll_offset;
+        self.page_heights = tab.page_heights;
+        self.bookmarks = tab.bookmarks;
+        self.show_bookmarks_popup = tab.show_bookmarks_popup;
+    }
+
+    /// A short label for tab `idx`, reading live state for the active tab.
+    pub fn tab_title(&self, idx: usize) -> String {
+        if idx == self.active_tab {
+            self.archive_path
+                .as_ref()
+                .and_then(|p| p.file_name())
+                .and_then(|n| n.to_str())
+                .unwrap_or("Untitled")
+                .to_string()
+        } else {
+            self.tabs
+                .get(idx)
+                .map(ComicTab::title)
+                .unwrap_or_else(|| "Untitled".to_string())
+        }
+    }
+
+    /// Switch the active tab to `idx`, swapping its stashed state into the
+    /// flat fields.
+    pub fn switch_tab(&mut self, idx: usize) {
+        if idx == self.active_tab || idx >= self.tabs.len() {
+            return;
+        }
+        let outgoing = self.take_active_as_tab();
+        self.tabs[self.active_tab] = outgoing;
+        let incoming = std::mem::take(&mut self.tabs[idx]);
+        self.apply_tab(incoming);
+        self.active_tab = idx;
+    }
+
+    /// Close tab `idx`, activating a neighbouring tab (or going empty if it
+    /// was the last one open).
+    pub fn close_tab(&mut self, idx: usize) {
+        if idx >= self.tabs.len() {
+            return;
+        }
+        if idx != self.active_tab {
+            self.tabs.remove(idx);
+            if idx < self.active_tab {
+                self.active_tab -= 1;
+            }
+            return;
+        }
+
+        self.tabs.remove(idx);
+        if self.tabs.is_empty() {
+            let ui_logger = Arc::clone(&self.ui_logger);
+            *self = Self::default();
+            self.ui_logger = ui_logger;
+        } else {
+            let next = idx.min(self.tabs.len() - 1);
+            let incoming = std::mem::take(&mut self.tabs[next]);
+            self.apply_tab(incoming);
+            self.active_tab = next;
+        }
+    }
+
+    /// Re-run `list_images` against the already-open archive and reconcile
+    /// state after an external change is observed by `watcher`.
+    pub fn reload_from_disk(&mut self) {
+        let Some(archive) = self.archive.clone() else { return };
+        let new_filenames = match archive.lock() {
+            Ok(guard) => guard.list_images(),
+            Err(_) => return,
+        };
+        if new_filenames.is_empty() {
+            if let Ok(mut logger) = self.ui_logger.lock() {
+                logger.warn("Reload found no images; keeping previous view.", None);
+            }
+            return;
+        }
+        self.total_pages = new_filenames.len();
+        self.current_page = self.current_page.min(new_filenames.len().saturating_sub(1));
+        self.filenames = Some(new_filenames);
+        self.image_lru.lock().unwrap().clear();
+        self.thumbnail_cache.lock().unwrap().clear();
+        self.thumbnail_textures.clear();
+        self.texture_cache.clear();
+        self.has_initialised_zoom = false;
+        if let Ok(mut logger) = self.ui_logger.lock() {
+            logger.info("Reloaded (external change detected)", None);
+        }
+    }
+
+    /// Called whenever the page changes: resets zoom and pan, and evicts the
+    /// departing page's animation frames. This used to clear the whole
+    /// `TextureCache` on every turn, which discarded the neighbor textures
+    /// the prefetch pipeline had just warmed for nothing; static pages stay
+    /// cached and are reused instantly if the reader flips back.
     pub fn on_page_changed(&mut self) {
         self.has_initialised_zoom = false;
-        self.texture_cache.clear();
+        self.texture_cache.evict_animated_page(self.current_page);
         self.pan_offset = Vec2::ZERO;
+        // The preload window has moved on; in-flight decodes for the old
+        // window are no longer worth keeping.
+        self.page_decode_pool.bump_generation();
     }
 
     fn update_window_title(&self, ctx: &egui::Context) {
@@ -222,31 +743,70 @@ impl CBZViewerApp {
     pub fn preload_images(&mut self, ctx: &egui::Context, archive: Arc<Mutex<ImageArchive>>) {
         let filenames = self.filenames.clone().unwrap_or_default();
 
-        // Preload images for current view and next pages
+        // Preload images for current view and surrounding pages
         let mut pages_to_preload = vec![self.current_page];
         let read_ahead = if self.is_web_archive {
-            READ_AHEAD_WEB
+            self.settings.read_ahead_web
+        } else {
+            self.settings.read_ahead
+        };
+
+        // Weight the window toward the way the reader is actually heading:
+        // the trailing side only gets `READ_BEHIND`, just enough that
+        // reversing direction doesn't start from a cold cache. Continuous
+        // scroll mode is the exception — several pages are visible in the
+        // viewport at once and it's just as likely to scroll back up as
+        // down, so it always reads the full window both ways.
+        let (forward_window, backward_window) = if self.continuous_scroll_mode {
+            (read_ahead, read_ahead)
         } else {
-            READ_AHEAD
+            match self.page_direction {
+                PageDirection::Forward => (read_ahead, READ_BEHIND.min(read_ahead)),
+                PageDirection::Backward => (READ_BEHIND.min(read_ahead), read_ahead),
+            }
         };
 
-        for offset in 1..=read_ahead {
+        for offset in 1..=forward_window {
             let next = self.current_page + offset;
             if next < self.total_pages {
                 pages_to_preload.push(next);
             }
         }
+        for offset in 1..=backward_window {
+            if let Some(prev) = self.current_page.checked_sub(offset) {
+                pages_to_preload.push(prev);
+            }
+        }
+        // Tell the pool which pages are worth decoding, so a worker that
+        // picks up a job for a page that has since scrolled out of the
+        // window (a reader flipping pages quickly) drops it instead of
+        // decoding and caching something nobody will see.
+        let desired: HashSet<usize> = pages_to_preload.iter().copied().collect();
+        self.page_decode_pool.set_desired(desired);
+
+        // The actively displayed page(s) decode at a resolution matched to
+        // the viewport (see `active_page_max_dimension`) rather than always
+        // at full size; everything further down the read-ahead window is
+        // downsampled to `MAX_PRELOAD_DIMENSION` to keep the memory budget
+        // off the UI's critical path.
+        let displayed_pages: usize = if self.double_page_mode { 2 } else { 1 };
+        let active_max_dimension = self.active_page_max_dimension(ctx);
+        let filenames = Arc::new(filenames);
         for &page in &pages_to_preload {
-            let filenames = Arc::new(filenames.clone());
-            let archive = archive.clone();
-            let image_lru = self.image_lru.clone();
-            let loading_pages = self.loading_pages.clone();
-            let ctx = ctx.clone();
-            tokio::spawn(async move {
-                // Do NOT lock any mutex here before await!
-                let _ =
-                    load_image_async(page, filenames, archive, image_lru, loading_pages, ctx).await;
-            });
+            let max_dimension = if page < self.current_page + displayed_pages {
+                active_max_dimension
+            } else {
+                Some(MAX_PRELOAD_DIMENSION)
+            };
+            self.page_decode_pool.submit(
+                page,
+                filenames.clone(),
+                archive.clone(),
+                self.image_lru.clone(),
+                self.loading_pages.clone(),
+                self.page_errors.clone(),
+                max_dimension,
+            );
         }
     }
 
@@ -261,9 +821,9 @@ impl CBZViewerApp {
             }
         }
 
-        // Optionally, try thumbnail cache (not full-size)
+        // Optionally, try thumbnail cache (not full-size; first frame only)
         if let Some(thumb) = self.thumbnail_cache.lock().unwrap().get(&page_idx) {
-            return Some(thumb.clone());
+            return Some(thumb.first_frame().clone());
         }
 
         // Not found in cache
@@ -295,7 +855,11 @@ impl CBZViewerApp {
 
         if self.on_new_comic {
             self.on_new_comic = false;
-            if let Some(path) = crate::comic_filters!().set_file_name("Comic").save_file() {
+            let mut dialog = crate::comic_filters!().set_file_name("Comic");
+            if let Some(dir) = &self.recents.last_dir {
+                dialog = dialog.set_directory(dir);
+            }
+            if let Some(path) = dialog.save_file() {
                 let _ = ZipImageArchive::create_from_path(&path);
                 let _ = self.load_new_file(path);
                 return; // Prevent further update with old state
@@ -303,7 +867,11 @@ impl CBZViewerApp {
         }
         if self.on_open_comic {
             self.on_open_comic = false;
-            if let Some(path) = crate::comic_filters!().set_file_name("Comic").pick_file() {
+            let mut dialog = crate::comic_filters!().set_file_name("Comic");
+            if let Some(dir) = &self.recents.last_dir {
+                dialog = dialog.set_directory(dir);
+            }
+            if let Some(path) = dialog.pick_file() {
                 // let _ = self.load_new_file(path);
                 self.new_page = Some(path);
                 return; // Prevent further update with old state
@@ -311,12 +879,27 @@ impl CBZViewerApp {
         }
         if self.on_open_folder {
             self.on_open_folder = false;
-            if let Some(path) = rfd::FileDialog::new().pick_folder() {
+            let mut dialog = rfd::FileDialog::new();
+            if let Some(dir) = &self.recents.last_dir {
+                dialog = dialog.set_directory(dir);
+            }
+            if let Some(path) = dialog.pick_folder() {
                 // let _ = self.load_new_file(path);
                 self.new_page = Some(path);
                 return;
             }
         }
+        if self.on_open_library {
+            self.on_open_library = false;
+            let mut dialog = rfd::FileDialog::new();
+            if let Some(dir) = &self.recents.last_dir {
+                dialog = dialog.set_directory(dir);
+            }
+            if let Some(path) = dialog.pick_folder() {
+                self.library.open_folder(path);
+                return;
+            }
+        }
         // Handle the async image saving outside the closure
         if self.on_save_image {
             self.on_save_image = false;
@@ -407,6 +990,21 @@ impl CBZViewerApp {
         if ctx.input(|i| i.key_pressed(egui::Key::ArrowLeft)) {
             self.goto_prev_page();
         }
+
+        // Browser-style tab shortcuts: Ctrl+Tab / Ctrl+Shift+Tab cycle tabs,
+        // Ctrl+W closes the active one.
+        if !self.tabs.is_empty() {
+            let tab_count = self.tabs.len();
+            if ctx.input(|i| i.modifiers.ctrl && !i.modifiers.shift && i.key_pressed(egui::Key::Tab)) {
+                self.switch_tab((self.active_tab + 1) % tab_count);
+            }
+            if ctx.input(|i| i.modifiers.ctrl && i.modifiers.shift && i.key_pressed(egui::Key::Tab)) {
+                self.switch_tab((self.active_tab + tab_count - 1) % tab_count);
+            }
+            if ctx.input(|i| i.modifiers.ctrl && i.key_pressed(egui::Key::W)) {
+                self.close_tab(self.active_tab);
+            }
+        }
     }
 }
 
@@ -423,13 +1021,24 @@ impl eframe::App for CBZViewerApp {
         // Load file synchronously to avoid borrow checker issues
         if let Some(path) = self.new_page.take() {
             if let Err(e) = futures::executor::block_on(self.load_new_file(path.clone())) {
-                if let Ok(mut logger) = self.ui_logger.lock() {
+                if matches!(e, AppError::Encrypted) {
+                    self.pending_password_prompt = Some(path);
+                    if let Ok(mut logger) = self.ui_logger.lock() {
+                        logger.warn("Archive is password-protected; enter the password to open it.", None);
+                    }
+                } else if let Ok(mut logger) = self.ui_logger.lock() {
                     logger.error(format!("Failed to load file: {}", e), None);
                 }
             }
             self.new_page = None;
         }
 
+        if let Some(watcher) = &self.watcher {
+            if watcher.poll_changed() {
+                self.reload_from_disk();
+            }
+        }
+
         self.update_window_title(ctx);
 
         // Only preload images if we have an archive and not in manifest editor mode
@@ -466,6 +1075,11 @@ impl eframe::App for CBZViewerApp {
         }
 
         self.display_debug_menu(ctx);
+        self.display_log_history(ctx);
+        self.display_recents_panel(ctx);
+        self.display_file_browser(ctx);
+        self.display_library_browser(ctx);
+        self.display_settings_modal(ctx);
         self.on_changes();
 
         // Draw the top and bottom bars
@@ -492,5 +1106,109 @@ impl eframe::App for CBZViewerApp {
                     }
                 });
         }
+
+        // Offer to resume at the saved "last read" position for a freshly
+        // opened archive, rather than jumping there unasked.
+        if let Some(page) = self.resume_prompt {
+            let mut resolved = false;
+            egui::Window::new("Resume reading?")
+                .collapsible(false)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    ui.label(format!("You last read up to page {}.", page + 1));
+                    ui.horizontal(|ui| {
+                        if ui.button("Resume").clicked() {
+                            self.goto_page(page);
+                            resolved = true;
+                        }
+                        if ui.button("Start from beginning").clicked() {
+                            resolved = true;
+                        }
+                    });
+                });
+            if resolved {
+                self.resume_prompt = None;
+            }
+        }
+
+        // Prompt for a password when `new_page` turned out to be encrypted.
+        if let Some(path) = self.pending_password_prompt.clone() {
+            let mut cancelled = false;
+            let mut submitted = false;
+            egui::Window::new("Password required")
+                .collapsible(false)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    ui.label(format!(
+                        "{} is password-protected.",
+                        path.file_name().and_then(|n| n.to_str()).unwrap_or("This archive")
+                    ));
+                    ui.add(egui::TextEdit::singleline(&mut self.password_input).password(true));
+                    ui.horizontal(|ui| {
+                        if ui.button("Open").clicked() {
+                            submitted = true;
+                        }
+                        if ui.button("Cancel").clicked() {
+                            cancelled = true;
+                        }
+                    });
+                });
+            if submitted {
+                let password = std::mem::take(&mut self.password_input);
+                match futures::executor::block_on(
+                    self.load_new_file_with_password(path, &password),
+                ) {
+                    Ok(()) => self.pending_password_prompt = None,
+                    Err(e) => {
+                        if let Ok(mut logger) = self.ui_logger.lock() {
+                            logger.error(format!("Failed to open password-protected archive: {}", e), None);
+                        }
+                    }
+                }
+            } else if cancelled {
+                self.pending_password_prompt = None;
+                self.password_input.clear();
+            }
+        }
+
+        // Show the bookmarks popup if enabled
+        if self.show_bookmarks_popup {
+            let mut goto = None;
+            let mut remove = None;
+            let mut relabelled = false;
+            egui::Window::new("Bookmarks")
+                .collapsible(false)
+                .resizable(true)
+                .show(ctx, |ui| {
+                    if self.bookmarks.is_empty() {
+                        ui.label("No bookmarks yet.");
+                    }
+                    for bookmark in &mut self.bookmarks {
+                        ui.horizontal(|ui| {
+                            if ui.button("\u{f061}").on_hover_text("Jump to this page").clicked() {
+                                goto = Some(bookmark.page);
+                            }
+                            if ui.text_edit_singleline(&mut bookmark.label).changed() {
+                                relabelled = true;
+                            }
+                            if ui.small_button("\u{e5cd}").clicked() {
+                                remove = Some(bookmark.page);
+                            }
+                        });
+                    }
+                    if ui.button("Close").clicked() {
+                        self.show_bookmarks_popup = false;
+                    }
+                });
+            if let Some(page) = goto {
+                self.goto_page(page);
+            }
+            if let Some(page) = remove {
+                self.toggle_bookmark(page);
+            }
+            if relabelled {
+                self.save_bookmarks();
+            }
+        }
     }
 }
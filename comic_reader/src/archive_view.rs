@@ -88,16 +88,23 @@ impl ArchiveView {
         for &page in &pages_to_preload {
             let filenames = Arc::new(filenames.clone());
             let archive = self.archive.clone().unwrap();
-            let image_lru = new_image_cache(CACHE_SIZE);
+            let image_lru = new_image_cache(CACHE_MEMORY_BUDGET_BYTES);
             let loading_pages = self.loading_pages.clone();
             let ctx = ctx.clone();
+            let max_dimension = if page == self.current_page {
+                None
+            } else {
+                Some(MAX_PRELOAD_DIMENSION)
+            };
             tokio::spawn(async move {
-                let _ = load_image_async(page, filenames, archive, image_lru, loading_pages, ctx).await;
+                let _ =
+                    load_image_async(page, filenames, archive, image_lru, loading_pages, ctx, max_dimension)
+                        .await;
             });
         }
     }
 
-    pub fn get_image_from_cache(&self, image_lru: &SharedImageCache, thumbnail_cache: &Arc<Mutex<std::collections::HashMap<usize, image::DynamicImage>>>, page_idx: usize) -> Option<image::DynamicImage> {
+    pub fn get_image_from_cache(&self, image_lru: &SharedImageCache, thumbnail_cache: &Arc<Mutex<std::collections::HashMap<usize, ThumbImage>>>, page_idx: usize) -> Option<image::DynamicImage> {
         use crate::cache::image_cache::PageImage;
         if let Some(entry) = image_lru.lock().unwrap().get(&page_idx) {
             if let PageImage::Static(ref dyn_img) = entry.image {
@@ -105,7 +112,7 @@ impl ArchiveView {
             }
         }
         if let Some(thumb) = thumbnail_cache.lock().unwrap().get(&page_idx) {
-            return Some(thumb.clone());
+            return Some(thumb.first_frame().clone());
         }
         None
     }
@@ -0,0 +1,178 @@
+//! Fetches and caches the `unrar`/`rar`/`7z` helper binaries when they
+//! aren't already on PATH, so RAR/7z support works without asking the user
+//! to install anything first.
+//!
+//! Downloads land under `dirs::cache_dir()/comic_suite/bin/<key>`, where
+//! `<key>` is a SipHash of the tool name, version, and source URL (stable
+//! across launches, so a tool fetched once is reused rather than
+//! re-downloaded) and are checked against an embedded SHA-256 before being
+//! unpacked. `ensure_tool_available` is the single entry point that used to
+//! be three separate `check_bin` calls in `main.rs`.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::io::Cursor;
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+
+/// A known-good build of a helper binary, fetched over HTTPS on demand.
+pub struct ToolSource {
+    /// Name as looked up via `which` and invoked afterwards (e.g. `"unrar"`).
+    pub tool: &'static str,
+    /// Human-readable label shown in the download prompt.
+    pub version: &'static str,
+    pub url: &'static str,
+    /// Lowercase hex SHA-256 of the downloaded archive.
+    pub sha256: &'static str,
+    /// Path to the executable inside the unpacked archive.
+    pub entry_path: &'static str,
+    pub kind: ArchiveKind,
+}
+
+pub enum ArchiveKind {
+    TarGz,
+    Zip,
+}
+
+fn cache_root() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("comic_suite")
+        .join("bin")
+}
+
+/// A stable per-source cache key, so the same (tool, version, url) always
+/// unpacks to the same directory and a later launch can reuse it.
+fn cache_key(source: &ToolSource) -> String {
+    let mut hasher = DefaultHasher::new();
+    source.tool.hash(&mut hasher);
+    source.version.hash(&mut hasher);
+    source.url.hash(&mut hasher);
+    format!("{:016x}", hasher.finish())
+}
+
+/// Make sure `source.tool` resolves via `which`, downloading and caching a
+/// known-good build first if it doesn't (after confirming with the user).
+/// Returns the directory to prepend to PATH, or `None` if nothing needs to
+/// change (already on PATH, download declined, or download failed).
+pub fn ensure_tool_available(source: &ToolSource) -> Option<PathBuf> {
+    if which::which(source.tool).is_ok() {
+        log::info!("'{}' found in PATH.", source.tool);
+        return None;
+    }
+
+    let dest_dir = cache_root().join(cache_key(source));
+    let entry = dest_dir.join(source.entry_path);
+    if entry.exists() {
+        log::info!("Using cached '{}' at {:?}", source.tool, dest_dir);
+        return Some(dest_dir);
+    }
+
+    if !confirm_download(source) {
+        log::warn!(
+            "'{}' not found on PATH and download declined; related archives will not open.",
+            source.tool
+        );
+        return None;
+    }
+
+    match download_and_unpack(source, &dest_dir) {
+        Ok(()) => {
+            log::info!("Fetched '{}' into {:?}", source.tool, dest_dir);
+            Some(dest_dir)
+        }
+        Err(e) => {
+            log::warn!("Failed to fetch '{}': {}", source.tool, e);
+            rfd::MessageDialog::new()
+                .set_title(&format!("Couldn't fetch {}", source.tool))
+                .set_description(&format!(
+                    "'{}' is not on PATH and the automatic download failed:\n{}",
+                    source.tool, e
+                ))
+                .set_buttons(rfd::MessageButtons::Ok)
+                .set_level(rfd::MessageLevel::Error)
+                .show();
+            None
+        }
+    }
+}
+
+fn confirm_download(source: &ToolSource) -> bool {
+    rfd::MessageDialog::new()
+        .set_title(&format!("Download {}?", source.tool))
+        .set_description(&format!(
+            "'{}' was not found on PATH. Download a known-good build ({}) to enable this archive format?",
+            source.tool, source.version
+        ))
+        .set_buttons(rfd::MessageButtons::YesNo)
+        .set_level(rfd::MessageLevel::Info)
+        .show()
+        == rfd::MessageDialogResult::Yes
+}
+
+fn download_and_unpack(source: &ToolSource, dest_dir: &Path) -> Result<(), String> {
+    let bytes = reqwest::blocking::get(source.url)
+        .and_then(|r| r.bytes())
+        .map_err(|e| format!("download failed: {e}"))?;
+
+    let mut hasher = Sha256::new();
+    hasher.update(&bytes);
+    let digest_hex = hasher
+        .finalize()
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect::<String>();
+    if digest_hex != source.sha256 {
+        return Err(format!(
+            "hash mismatch for {} (expected {}, got {})",
+            source.url, source.sha256, digest_hex
+        ));
+    }
+
+    std::fs::create_dir_all(dest_dir).map_err(|e| format!("couldn't create cache dir: {e}"))?;
+
+    match source.kind {
+        ArchiveKind::TarGz => {
+            let decoder = flate2::read::GzDecoder::new(Cursor::new(bytes.as_ref()));
+            tar::Archive::new(decoder)
+                .unpack(dest_dir)
+                .map_err(|e| format!("couldn't unpack tar.gz: {e}"))?;
+        }
+        ArchiveKind::Zip => {
+            let mut archive = zip::ZipArchive::new(Cursor::new(bytes.as_ref()))
+                .map_err(|e| format!("couldn't open zip: {e}"))?;
+            archive
+                .extract(dest_dir)
+                .map_err(|e| format!("couldn't unpack zip: {e}"))?;
+        }
+    }
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let entry = dest_dir.join(source.entry_path);
+        if let Ok(meta) = std::fs::metadata(&entry) {
+            let mut perms = meta.permissions();
+            perms.set_mode(perms.mode() | 0o111);
+            let _ = std::fs::set_permissions(&entry, perms);
+        }
+    }
+
+    Ok(())
+}
+
+/// Prepend `dir` to the running process's PATH, so `Command::new(tool)`
+/// resolves to the freshly-unpacked binary for the rest of this session.
+pub fn prepend_to_path(dir: &Path) {
+    let path_var = std::env::var_os("PATH").unwrap_or_default();
+    let mut paths: Vec<PathBuf> = vec![dir.to_path_buf()];
+    paths.extend(std::env::split_paths(&path_var));
+    if let Ok(joined) = std::env::join_paths(paths) {
+        unsafe {
+            std::env::set_var("PATH", joined);
+        }
+    } else {
+        log::warn!("Couldn't prepend {:?} to PATH", dir);
+    }
+}
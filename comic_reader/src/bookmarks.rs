@@ -0,0 +1,84 @@
+//! Per-archive bookmarks, persisted across restarts in a single TOML file in
+//! the user's config directory and keyed by archive identity (its
+//! canonicalized path), so the same comic reopened later restores both its
+//! user-placed marks and the automatic "last read" position.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+/// A single marked page.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Bookmark {
+    pub page: usize,
+    pub label: String,
+}
+
+/// Everything recorded for one archive: user-placed marks plus the
+/// automatically-updated "last read" page.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ArchiveBookmarks {
+    pub marks: Vec<Bookmark>,
+    pub last_read: Option<usize>,
+}
+
+/// All archives' bookmarks, keyed by archive identity.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct BookmarkStore {
+    pub archives: HashMap<String, ArchiveBookmarks>,
+}
+
+fn bookmarks_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("comic_reader")
+        .join("bookmarks.toml")
+}
+
+/// The key used to identify an archive in the store, so the same comic
+/// resolves to the same entry regardless of the relative path it was opened
+/// from.
+fn archive_key(path: &Path) -> String {
+    path.canonicalize()
+        .unwrap_or_else(|_| path.to_path_buf())
+        .to_string_lossy()
+        .into_owned()
+}
+
+impl BookmarkStore {
+    /// Load the store from disk, falling back to empty if missing or invalid.
+    pub fn load() -> Self {
+        std::fs::read_to_string(bookmarks_path())
+            .ok()
+            .and_then(|s| toml::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    /// Persist the store to disk, creating the config directory if needed.
+    pub fn save(&self) {
+        let path = bookmarks_path();
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        if let Ok(toml) = toml::to_string_pretty(self) {
+            let _ = std::fs::write(path, toml);
+        }
+    }
+
+    /// Bookmarks recorded for `path`, or an empty set if none exist yet.
+    pub fn for_archive(&self, path: &Path) -> ArchiveBookmarks {
+        self.archives
+            .get(&archive_key(path))
+            .cloned()
+            .unwrap_or_default()
+    }
+
+    /// Replace the bookmarks recorded for `path` and persist the change.
+    pub fn set_for_archive(&mut self, path: &Path, bookmarks: ArchiveBookmarks) {
+        self.archives.insert(archive_key(path), bookmarks);
+        self.save();
+    }
+}
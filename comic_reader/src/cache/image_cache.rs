@@ -1,26 +1,53 @@
 //! LRU cache for decoded images and async image loading.
 
 use crate::prelude::*;
-use std::io::Cursor;
+use std::io::{Cursor, Read, Seek, SeekFrom, Write};
+use std::sync::atomic::{AtomicU64, Ordering};
 
 use futures::executor::block_on;
 #[cfg(feature = "webp_animation")]
 use webp_animation::Decoder as WebpAnimDecoder;
 
-/// Represents a decoded page image (static or animated).
+/// Byte range (plus dimensions) of one composited RGBA8 frame within an
+/// animated page's scratch file, so playback can seek straight to a frame
+/// without re-running the palette/disposal compositor.
+#[derive(Clone, Copy)]
+pub struct ScratchFrame {
+    pub offset: u64,
+    pub len: u64,
+    pub width: u32,
+    pub height: u32,
+}
+
+/// Represents a decoded page image (static, animated, or vector).
 #[derive(Clone)]
 pub enum PageImage {
     Static(DynamicImage),
-    AnimatedGif {
-        frames: Vec<egui::TextureHandle>,
-        delays: Vec<u16>,
-        start_time: Instant,
-    },
-    AnimatedWebP {
-        frames: Vec<egui::TextureHandle>,
+    /// A decoded multi-frame animation (GIF, animated WebP, APNG, or
+    /// Aseprite), all fed through this one format-agnostic variant so
+    /// `draw_gif_at_rect`'s playback logic doesn't need a per-format copy.
+    /// Frames are composited once at decode time and streamed out to
+    /// `scratch_path` rather than held as a `Vec` of full frames, so a
+    /// long animation's resident memory is bounded by
+    /// `ANIMATION_FRAME_RING_SIZE` instead of its total frame count. The
+    /// draw path (see `ui::image::draw_gif_at_rect`) reads back only the
+    /// frame(s) it's about to display.
+    Animated {
+        scratch_path: Arc<std::path::PathBuf>,
+        frame_table: Arc<Vec<ScratchFrame>>,
         delays: Vec<u16>,
         start_time: Instant,
+        /// How many times the animation should cycle before holding on its
+        /// final frame. `None` (the common case, since none of our decoders
+        /// currently surface the container's loop count) means loop forever.
+        loop_count: Option<u32>,
     },
+    /// An SVG page kept as its parsed tree rather than a pre-rasterized
+    /// bitmap, so the draw path can re-rasterize it at the display's
+    /// current zoom instead of blurring a fixed-resolution texture up or
+    /// down. See `ui::image::draw_vector_page`.
+    #[cfg(feature = "svg")]
+    Vector(Arc<usvg::Tree>),
 }
 
 impl PageImage {
@@ -28,54 +55,223 @@ impl PageImage {
     pub fn dimensions(&self) -> (u32, u32) {
         match self {
             PageImage::Static(img) => img.dimensions(),
-            PageImage::AnimatedGif { frames, .. } | PageImage::AnimatedWebP { frames, .. } => {
-                if let Some(frame) = frames.first() {
-                    (frame.size()[0] as u32, frame.size()[1] as u32)
+            PageImage::Animated { frame_table, .. } => {
+                if let Some(frame) = frame_table.first() {
+                    (frame.width, frame.height)
                 } else {
                     (0, 0)
                 }
             }
+            #[cfg(feature = "svg")]
+            PageImage::Vector(tree) => {
+                let size = tree.size();
+                (size.width().ceil() as u32, size.height().ceil() as u32)
+            }
         }
     }
 }
 
+/// A process-wide unique path under the OS temp dir to stream one
+/// animated page's composited frames to, so concurrent decodes (and
+/// concurrent archive tabs) never collide on the same file.
+fn new_scratch_path() -> std::path::PathBuf {
+    static COUNTER: AtomicU64 = AtomicU64::new(0);
+    let id = COUNTER.fetch_add(1, Ordering::Relaxed);
+    std::env::temp_dir().join(format!(
+        "comic_reader_anim_{}_{}.rgba",
+        std::process::id(),
+        id
+    ))
+}
+
+/// Read back a single composited frame from `path` at the location
+/// recorded in `frame`, reconstructing it as a `ColorImage` ready for
+/// `ctx.load_texture`.
+pub fn read_scratch_frame(path: &std::path::Path, frame: &ScratchFrame) -> Option<egui::ColorImage> {
+    let mut file = std::fs::File::open(path).ok()?;
+    file.seek(SeekFrom::Start(frame.offset)).ok()?;
+    let mut buf = vec![0u8; frame.len as usize];
+    file.read_exact(&mut buf).ok()?;
+    Some(egui::ColorImage::from_rgba_unmultiplied(
+        [frame.width as usize, frame.height as usize],
+        &buf,
+    ))
+}
+
 /// A loaded page, ready for display.
 #[derive(Clone)]
 pub struct LoadedPage {
     pub image: PageImage,
     pub index: usize,
     pub filename: String,
+    /// Set when `image` was downsampled to `MAX_PRELOAD_DIMENSION` on decode
+    /// (a read-ahead page) rather than decoded at full resolution (the
+    /// actively displayed page, or a "save image" fetch).
+    pub downsampled: bool,
 }
 
-/// Shared LRU cache for images.
-pub type SharedImageCache = Arc<Mutex<LruCache<usize, LoadedPage>>>;
-
-/// Create a new shared LRU cache for images.
-pub fn new_image_cache(size: usize) -> SharedImageCache {
-    Arc::new(Mutex::new(LruCache::new(NonZeroUsize::new(size).unwrap())))
-}
-
-// Macro to extract frames and delays and upload as egui textures
-macro_rules! extract_animation_frames {
-    ($frames:expr, $delays:expr, $ctx:expr) => {{
-        let mut textures = Vec::with_capacity($frames.len());
-        for (i, color_image) in $frames.into_iter().enumerate() {
-            let handle = $ctx.load_texture(
-                format!("anim_frame_{}", i),
-                color_image,
-                egui::TextureOptions::default(),
-            );
-            textures.push(handle);
+impl LoadedPage {
+    /// Approximate decoded size in bytes, as if every frame were RGBA8.
+    /// Used to weigh entries in `ImageLruCache`'s memory budget.
+    pub fn approx_bytes(&self) -> usize {
+        let (w, h) = self.image.dimensions();
+        let frames = match &self.image {
+            PageImage::Static(_) => 1,
+            PageImage::Animated { frame_table, .. } => frame_table.len().max(1),
+            // The parsed tree is tiny next to a rasterized page; rasterized
+            // frames live in `TextureCache`, not here.
+            #[cfg(feature = "svg")]
+            PageImage::Vector(_) => 0,
+        };
+        (w as usize) * (h as usize) * 4 * frames
+    }
+}
+
+/// LRU cache for decoded images, evicted by an approximate memory budget
+/// (sum of `LoadedPage::approx_bytes`) rather than a fixed entry count, so a
+/// handful of large scanned pages can't starve the rest of the read-ahead
+/// window.
+pub struct ImageLruCache {
+    entries: LruCache<usize, LoadedPage>,
+    budget_bytes: usize,
+    used_bytes: usize,
+}
+
+impl ImageLruCache {
+    fn new(budget_bytes: usize) -> Self {
+        Self {
+            // The entry count is effectively unbounded; `put` evicts on
+            // `used_bytes` instead.
+            entries: LruCache::unbounded(),
+            budget_bytes,
+            used_bytes: 0,
+        }
+    }
+
+    pub fn get(&mut self, key: &usize) -> Option<&LoadedPage> {
+        self.entries.get(key)
+    }
+
+    pub fn put(&mut self, key: usize, value: LoadedPage) {
+        if let Some(old) = self.entries.put(key, value.clone()) {
+            self.used_bytes = self.used_bytes.saturating_sub(old.approx_bytes());
+        }
+        self.used_bytes += value.approx_bytes();
+
+        while self.used_bytes > self.budget_bytes && self.entries.len() > 1 {
+            if let Some((_, evicted)) = self.entries.pop_lru() {
+                self.used_bytes = self.used_bytes.saturating_sub(evicted.approx_bytes());
+            } else {
+                break;
+            }
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&usize, &LoadedPage)> {
+        self.entries.iter()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.used_bytes = 0;
+    }
+
+    /// The configured memory budget, in bytes.
+    pub fn budget_bytes(&self) -> usize {
+        self.budget_bytes
+    }
+
+    /// Change the memory budget at runtime (see `ui::settings_modal`),
+    /// evicting immediately if the new budget is smaller than what's
+    /// currently held.
+    pub fn set_budget_bytes(&mut self, budget_bytes: usize) {
+        self.budget_bytes = budget_bytes;
+        while self.used_bytes > self.budget_bytes && self.entries.len() > 1 {
+            if let Some((_, evicted)) = self.entries.pop_lru() {
+                self.used_bytes = self.used_bytes.saturating_sub(evicted.approx_bytes());
+            } else {
+                break;
+            }
         }
-        (textures, $delays)
-    }};
+    }
+
+    /// The approximate number of decoded-pixel bytes currently held.
+    pub fn used_bytes(&self) -> usize {
+        self.used_bytes
+    }
+}
+
+/// Shared LRU cache for images.
+pub type SharedImageCache = Arc<Mutex<ImageLruCache>>;
+
+/// Create a new shared, memory-budgeted LRU cache for images.
+pub fn new_image_cache(budget_bytes: usize) -> SharedImageCache {
+    Arc::new(Mutex::new(ImageLruCache::new(budget_bytes)))
+}
+
+/// Downsample `img` so neither dimension exceeds `max_dim`, preserving
+/// aspect ratio. Images already within the cap are returned untouched.
+///
+/// Read-ahead pages are never the one on screen at full zoom, so they're
+/// resized with the cheap `Triangle` filter to keep decoding off the UI's
+/// critical path. A page being shrunk a lot, though, is worth the extra
+/// `Lanczos3` cost: it's the actively viewed page (see
+/// `CBZViewerApp::active_page_max_dimension`) and a small/mild resize
+/// wouldn't show the difference anyway.
+fn cap_dimension(img: DynamicImage, max_dim: u32) -> DynamicImage {
+    let (w, h) = img.dimensions();
+    if w <= max_dim && h <= max_dim {
+        return img;
+    }
+    let shrink_ratio = max_dim as f32 / w.max(h) as f32;
+    let filter = if shrink_ratio < DOWNSCALE_QUALITY_THRESHOLD {
+        image::imageops::FilterType::Lanczos3
+    } else {
+        image::imageops::FilterType::Triangle
+    };
+    img.resize(max_dim, max_dim, filter)
+}
+
+/// Stream a decoded animation's composited frames out to a fresh scratch
+/// file on disk, recording each frame's byte range so playback can read
+/// a single frame back on demand instead of keeping the whole sequence
+/// resident. Returns `None` if the scratch file couldn't be created or
+/// written (falls back to the static decode at the call site).
+fn stream_frames_to_scratch(
+    frames: Vec<egui::ColorImage>,
+    delays: Vec<u16>,
+) -> Option<(Arc<std::path::PathBuf>, Arc<Vec<ScratchFrame>>, Vec<u16>)> {
+    let path = new_scratch_path();
+    let mut file = std::fs::File::create(&path).ok()?;
+    let mut table = Vec::with_capacity(frames.len());
+    let mut offset = 0u64;
+    for color_image in frames {
+        let [width, height] = color_image.size;
+        let bytes: Vec<u8> = color_image
+            .pixels
+            .iter()
+            .flat_map(|c| c.to_array())
+            .collect();
+        file.write_all(&bytes).ok()?;
+        table.push(ScratchFrame {
+            offset,
+            len: bytes.len() as u64,
+            width: width as u32,
+            height: height as u32,
+        });
+        offset += bytes.len() as u64;
+    }
+    Some((Arc::new(path), Arc::new(table), delays))
 }
 
 #[cfg(feature = "webp_animation")]
 fn try_decode_animated_webp(
     buf: &[u8],
-    ctx: &egui::Context,
-) -> Option<(Vec<egui::TextureHandle>, Vec<u16>)> {
+) -> Option<(Arc<std::path::PathBuf>, Arc<Vec<ScratchFrame>>, Vec<u16>)> {
     let decoder = WebpAnimDecoder::new(buf).ok()?;
     let mut frames = Vec::new();
     let mut delays = Vec::new();
@@ -99,13 +295,23 @@ fn try_decode_animated_webp(
         frames.push(color_image);
     }
     if frames.len() > 1 {
-        let (textures, delays) = extract_animation_frames!(frames, delays, ctx);
-        Some((textures, delays))
+        stream_frames_to_scratch(frames, delays)
     } else {
         None
     }
 }
-fn decode_gif(buf: &[u8], ctx: &egui::Context) -> Option<(Vec<egui::TextureHandle>, Vec<u16>)> {
+
+/// Floor a per-frame delay to at least 20ms (50 FPS), the same clamp
+/// `try_decode_animated_webp` already applies. Some GIF/APNG encoders emit a
+/// delay of 0 to mean "as fast as possible", which would otherwise make
+/// `draw_gif_at_rect` busy-loop requesting repaints every frame.
+fn normalize_delay(delay_ms: u16) -> u16 {
+    delay_ms.max(20)
+}
+
+fn decode_gif(
+    buf: &[u8],
+) -> Option<(Arc<std::path::PathBuf>, Arc<Vec<ScratchFrame>>, Vec<u16>)> {
     let cursor = Cursor::new(buf);
     let decoder = GifDecoder::new(cursor).ok()?;
     let frames = decoder.into_frames().collect::<Result<Vec<_>, _>>().ok()?;
@@ -115,7 +321,7 @@ fn decode_gif(buf: &[u8], ctx: &egui::Context) -> Option<(Vec<egui::TextureHandl
 
     for frame in frames {
         let delay = frame.delay().numer_denom_ms().0; // delay numerator (ms)
-        delays.push(delay as u16);
+        delays.push(normalize_delay(delay as u16));
         let buffer = frame.buffer();
         let color_image = egui::ColorImage::from_rgba_unmultiplied(
             [buffer.width() as usize, buffer.height() as usize],
@@ -124,21 +330,94 @@ fn decode_gif(buf: &[u8], ctx: &egui::Context) -> Option<(Vec<egui::TextureHandl
         color_frames.push(color_image);
     }
     if color_frames.len() > 1 {
-        let (textures, delays) = extract_animation_frames!(color_frames, delays, ctx);
-        Some((textures, delays))
+        stream_frames_to_scratch(color_frames, delays)
+    } else {
+        None
+    }
+}
+
+/// Decode an Aseprite document's frames via `comic_archive::decode`, reusing
+/// the same scratch-file layout as `decode_gif`/`try_decode_animated_webp`.
+/// Returns `None` for a single-frame (non-animated) Aseprite file so the
+/// caller falls through to the ordinary static decode.
+///
+/// Feeds the same `PageImage::Animated` variant the GIF/WebP/APNG paths use
+/// rather than a dedicated `AnimatedAse` case, since playback, ring-buffer
+/// eviction, and dimension lookup are identical once frames are composited
+/// into RGBA — a format-specific variant would just duplicate that logic.
+#[cfg(feature = "aseprite")]
+fn decode_aseprite(
+    filename: &str,
+    buf: &[u8],
+) -> Option<(Arc<std::path::PathBuf>, Arc<Vec<ScratchFrame>>, Vec<u16>)> {
+    let frames = comic_archive::decode::decode_frames(filename, buf).ok()?;
+    if frames.len() <= 1 {
+        return None;
+    }
+    let mut color_frames = Vec::with_capacity(frames.len());
+    let mut delays = Vec::with_capacity(frames.len());
+    for frame in frames {
+        delays.push(normalize_delay(frame.delay_ms));
+        color_frames.push(egui::ColorImage::from_rgba_unmultiplied(
+            [frame.width as usize, frame.height as usize],
+            &frame.rgba,
+        ));
+    }
+    stream_frames_to_scratch(color_frames, delays)
+}
+
+/// Decode an animated PNG's frames, reusing the same per-frame layout as
+/// `decode_gif`/`try_decode_animated_webp` so the three feed the same
+/// `PageImage::Animated` variant. Returns `None` for a plain (non-animated)
+/// PNG so the caller falls through to the ordinary static decode.
+fn decode_apng(
+    buf: &[u8],
+) -> Option<(Arc<std::path::PathBuf>, Arc<Vec<ScratchFrame>>, Vec<u16>)> {
+    let decoder = image::codecs::png::PngDecoder::new(Cursor::new(buf)).ok()?;
+    if !decoder.is_apng().ok()? {
+        return None;
+    }
+    let frames = decoder
+        .apng()
+        .ok()?
+        .into_frames()
+        .collect::<Result<Vec<_>, _>>()
+        .ok()?;
+
+    let mut color_frames = Vec::with_capacity(frames.len());
+    let mut delays = Vec::with_capacity(frames.len());
+    for frame in frames {
+        let delay = frame.delay().numer_denom_ms().0;
+        delays.push(normalize_delay(delay as u16));
+        let buffer = frame.buffer();
+        let color_image = egui::ColorImage::from_rgba_unmultiplied(
+            [buffer.width() as usize, buffer.height() as usize],
+            buffer.as_raw(),
+        );
+        color_frames.push(color_image);
+    }
+    if color_frames.len() > 1 {
+        stream_frames_to_scratch(color_frames, delays)
     } else {
         None
     }
 }
 
 /// Asynchronously load an image from the archive and insert into the cache.
+///
+/// `max_dimension` caps the decoded size of static pages (read-ahead), as a
+/// memory-saving tradeoff; pass `None` for the actively displayed page so it
+/// always lands in the cache at full resolution. An existing downsampled
+/// cache entry is replaced rather than reused when a full-resolution load is
+/// requested for the same page.
 pub async fn load_image_async(
     page: usize,
     filenames: Arc<Vec<String>>,
     archive: Arc<Mutex<ImageArchive>>,
     image_lru: SharedImageCache,
     loading_pages: Arc<Mutex<std::collections::HashSet<usize>>>,
-    ctx: egui::Context,
+    _ctx: egui::Context,
+    max_dimension: Option<u32>,
 ) -> Result<(), AppError> {
     {
         let mut loading = loading_pages.lock().unwrap();
@@ -148,13 +427,51 @@ pub async fn load_image_async(
         loading.insert(page);
     }
 
-    if image_lru.lock().unwrap().get(&page).is_some() {
+    let already_sufficient = match image_lru.lock().unwrap().get(&page) {
+        Some(entry) => max_dimension.is_some() || !entry.downsampled,
+        None => false,
+    };
+    if already_sufficient {
         loading_pages.lock().unwrap().remove(&page);
         return Ok(());
     }
 
     let filename = filenames[page].clone();
 
+    // Check the on-disk page cache before touching the archive at all: a
+    // hit is a cheap blob read instead of an archive extract + image decode,
+    // and survives between sessions unlike `image_lru`.
+    let page_cache_key = {
+        let archive_path = archive.lock().unwrap().path.clone();
+        let mtime = std::fs::metadata(&archive_path)
+            .and_then(|m| m.modified())
+            .ok();
+        comic_archive::page_cache::key(&archive_path, &filename, mtime, max_dimension)
+    };
+    {
+        let cache_key = page_cache_key.clone();
+        let cached = tokio::task::spawn_blocking(move || comic_archive::page_cache::read(&cache_key))
+            .await
+            .ok()
+            .flatten();
+        if let Some((width, height, rgba)) = cached {
+            if let Some(img) = image::RgbaImage::from_raw(width, height, rgba)
+                .map(DynamicImage::ImageRgba8)
+            {
+                let loaded_page = LoadedPage {
+                    image: PageImage::Static(img),
+                    index: page,
+                    filename: filename.clone(),
+                    downsampled: max_dimension.is_some(),
+                };
+                image_lru.lock().unwrap().put(page, loaded_page);
+                loading_pages.lock().unwrap().remove(&page);
+                debug!("Loaded image page {} from on-disk page cache", page);
+                return Ok(());
+            }
+        }
+    }
+
     // Read the image buffer in a blocking task to avoid holding the lock across .await
     let archive_clone = archive.clone();
     let buf: Vec<u8> = match tokio::task::spawn_blocking(move || {
@@ -179,58 +496,205 @@ pub async fn load_image_async(
     };
 
     let filename_clone = filename.clone();
-    let ctx_clone = ctx.clone();
     let image_lru_clone = image_lru.clone();
     let loading_pages_clone = loading_pages.clone();
 
     tokio::task::spawn_blocking(move || {
-        let loaded_page = if filename_clone.to_lowercase().ends_with(".gif") {
-            if let Some((frames, delays)) = decode_gif(&buf, &ctx_clone) {
-                PageImage::AnimatedGif {
-                    frames,
+        let (loaded_page, downsampled) = match decode_loaded_page(&filename_clone, &buf, max_dimension) {
+            Ok(result) => result,
+            Err(e) => {
+                loading_pages_clone.lock().unwrap().remove(&page);
+                debug!("Failed to decode image: {:?}", e);
+                return;
+            }
+        };
+
+        // Populate the on-disk page cache for static pages so the next time
+        // this archive is opened, this page is a blob read instead of a
+        // full archive-extract + decode. Animated/vector pages are skipped:
+        // their frame tables and parsed trees aren't a simple RGBA dump.
+        if let PageImage::Static(img) = &loaded_page {
+            let rgba = img.to_rgba8();
+            let (w, h) = rgba.dimensions();
+            comic_archive::page_cache::write(&page_cache_key, w, h, rgba.as_raw());
+        }
+
+        let loaded_page = LoadedPage {
+            image: loaded_page,
+            index: page,
+            filename: filename_clone,
+            downsampled,
+        };
+
+        image_lru_clone.lock().unwrap().put(page, loaded_page);
+        loading_pages_clone.lock().unwrap().remove(&page);
+        debug!("Loaded image page {} into LRU cache", page);
+    })
+    .await
+    .unwrap();
+
+    Ok(())
+}
+
+/// Decode `filename` as a plain static image via `decode_page`, falling
+/// back to the stock `image::load_from_memory` if the extension-specific
+/// path fails (e.g. a mis-extensioned file). Returns the underlying error
+/// from whichever attempt failed last instead of panicking, so a corrupt or
+/// genuinely unsupported page surfaces as a decode failure the caller can
+/// log and skip rather than crashing the viewer.
+fn decode_static_fallback(filename: &str, buf: &[u8]) -> Result<DynamicImage, ArchiveError> {
+    decode_page(filename, buf).or_else(|e| {
+        log::warn!("Falling back to raw decode for {}: {}", filename, e);
+        image::load_from_memory(buf).map_err(|e| ArchiveError::ImageProcessingError(e.to_string()))
+    })
+}
+
+/// Decode `filename`'s raw bytes into a `PageImage`, dispatching by
+/// extension the same way `load_image_async` always has, then downsample a
+/// static result to `max_dimension` if requested. Pulled out of
+/// `load_image_async` so `cache::page_worker_pool`'s bounded decode workers
+/// can share the exact same decode path instead of duplicating it.
+///
+/// GIF, APNG, and (with the `aseprite` feature) `.ase`/`.aseprite` pages are
+/// all first-class comic pages here, not just their first frame: each
+/// multi-frame source composites into the same `PageImage::Animated`
+/// representation and plays back through `ui::image::draw_gif_at_rect`,
+/// which advances frames off `Instant::now()` and the per-frame delay table
+/// and requests a repaint while a frame is still active.
+pub(crate) fn decode_loaded_page(
+    filename: &str,
+    buf: &[u8],
+    max_dimension: Option<u32>,
+) -> Result<(PageImage, bool), ArchiveError> {
+    // Animated pages stream their composited frames straight to a scratch
+    // file during decode rather than a `Vec`, so there's no cheap downsample
+    // step for them here; the memory cap only applies to static pages,
+    // which are the vast majority of large scanned archives.
+    let mut downsampled = false;
+    let filename_clone = filename;
+    let loaded_page = if filename_clone.to_lowercase().ends_with(".svg") {
+            // Keep SVG pages as a parsed tree instead of rasterizing once
+            // here: `decode_page` would lock them to a single fixed-size
+            // bitmap, blurry at any other zoom. The draw path rasterizes
+            // `Vector` fresh whenever the zoom level changes.
+            #[cfg(feature = "svg")]
+            {
+                match comic_archive::decode::parse_svg_tree(buf) {
+                    Ok(tree) => PageImage::Vector(Arc::new(tree)),
+                    Err(e) => {
+                        log::warn!("Falling back to raster decode for {}: {}", filename_clone, e);
+                        PageImage::Static(decode_static_fallback(filename_clone, buf)?)
+                    }
+                }
+            }
+            #[cfg(not(feature = "svg"))]
+            {
+                PageImage::Static(decode_static_fallback(filename_clone, buf)?)
+            }
+        } else if filename_clone.to_lowercase().ends_with(".gif") {
+            if let Some((scratch_path, frame_table, delays)) = decode_gif(buf) {
+                PageImage::Animated {
+                    scratch_path,
+                    frame_table,
                     delays,
                     start_time: Instant::now(),
+                    loop_count: None,
                 }
             } else {
-                let img = image::load_from_memory(&buf).unwrap();
-                PageImage::Static(img)
+                PageImage::Static(decode_static_fallback(filename_clone, buf)?)
             }
         } else if filename_clone.to_lowercase().ends_with(".webp") {
             #[cfg(feature = "webp_animation")]
             {
-                if let Some((frames, delays)) = try_decode_animated_webp(&buf, &ctx_clone) {
-                    PageImage::AnimatedWebP {
-                        frames,
+                if let Some((scratch_path, frame_table, delays)) = try_decode_animated_webp(buf) {
+                    PageImage::Animated {
+                        scratch_path,
+                        frame_table,
                         delays,
                         start_time: Instant::now(),
+                        loop_count: None,
                     }
                 } else {
-                    let img = image::load_from_memory(&buf).unwrap();
-                    PageImage::Static(img)
+                    PageImage::Static(decode_static_fallback(filename_clone, buf)?)
                 }
             }
             #[cfg(not(feature = "webp_animation"))]
             {
-                let img = image::load_from_memory(&buf).unwrap();
-                PageImage::Static(img)
+                PageImage::Static(decode_static_fallback(filename_clone, buf)?)
+            }
+        } else if filename_clone.to_lowercase().ends_with(".png") {
+            if let Some((scratch_path, frame_table, delays)) = decode_apng(buf) {
+                PageImage::Animated {
+                    scratch_path,
+                    frame_table,
+                    delays,
+                    start_time: Instant::now(),
+                    loop_count: None,
+                }
+            } else {
+                PageImage::Static(decode_static_fallback(filename_clone, buf)?)
+            }
+        } else if filename_clone.to_lowercase().ends_with(".ase")
+            || filename_clone.to_lowercase().ends_with(".aseprite")
+        {
+            #[cfg(feature = "aseprite")]
+            {
+                if let Some((scratch_path, frame_table, delays)) =
+                    decode_aseprite(filename_clone, buf)
+                {
+                    PageImage::Animated {
+                        scratch_path,
+                        frame_table,
+                        delays,
+                        start_time: Instant::now(),
+                        loop_count: None,
+                    }
+                } else {
+                    PageImage::Static(decode_static_fallback(filename_clone, buf)?)
+                }
+            }
+            #[cfg(not(feature = "aseprite"))]
+            {
+                PageImage::Static(decode_static_fallback(filename_clone, buf)?)
             }
         } else {
-            let img = image::load_from_memory(&buf).unwrap();
-            PageImage::Static(img)
+            PageImage::Static(decode_static_fallback(filename_clone, buf)?)
         };
 
-        let loaded_page = LoadedPage {
-            image: loaded_page,
-            index: page,
-            filename: filename_clone,
+        let loaded_page = if let (PageImage::Static(img), Some(max_dim)) =
+            (&loaded_page, max_dimension)
+        {
+            let (w, h) = img.dimensions();
+            let resized = cap_dimension(img.clone(), max_dim);
+            downsampled = resized.dimensions() != (w, h);
+            PageImage::Static(resized)
+        } else {
+            loaded_page
         };
 
-        image_lru_clone.lock().unwrap().put(page, loaded_page);
-        loading_pages_clone.lock().unwrap().remove(&page);
-        debug!("Loaded image page {} into LRU cache", page);
-    })
-    .await
-    .unwrap();
 
-    Ok(())
+        Ok((loaded_page, downsampled))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn decode_loaded_page_returns_an_error_instead_of_panicking_on_garbage_bytes() {
+        let result = decode_loaded_page("page.png", b"not actually a png", None);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn decode_loaded_page_decodes_a_well_formed_static_image() {
+        let mut bytes = Vec::new();
+        DynamicImage::new_rgba8(2, 2)
+            .write_to(&mut Cursor::new(&mut bytes), image::ImageFormat::Png)
+            .unwrap();
+
+        let (image, downsampled) = decode_loaded_page("page.png", &bytes, None).unwrap();
+        assert!(matches!(image, PageImage::Static(_)));
+        assert!(!downsampled);
+    }
 }
@@ -0,0 +1,8 @@
+//! Decoded-page and texture caching.
+
+pub mod image_cache;
+pub mod page_worker_pool;
+pub mod texture_cache;
+
+pub use image_cache::{SharedImageCache, load_image_async, new_image_cache};
+pub use page_worker_pool::PageDecodeWorkerPool;
@@ -0,0 +1,328 @@
+//! Bounded worker pool for decoding preload pages, replacing one
+//! `tokio::task::spawn` (and its abort-handle bookkeeping) per prefetched
+//! page with a small fixed set of threads pulling jobs off a bounded
+//! channel. Mirrors `texture_cache::TextureCache`'s own decode-worker pool,
+//! but for archive-read + image-decode instead of `LoadedPage` -> GPU
+//! upload conversion.
+//!
+//! Staleness is tracked with a generation counter (bumped whenever the
+//! reader moves to a different page or closes the document) instead of
+//! `JoinHandle::abort`: a job already mid-decode when its generation goes
+//! stale just finishes and drops its result rather than being interrupted,
+//! which is cheaper than plumbing cancellation through the archive-read and
+//! decode calls.
+
+use crate::prelude::*;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, SyncSender};
+use std::thread;
+
+/// Number of threads decoding preloaded pages concurrently.
+const WORKER_COUNT: usize = 2;
+
+/// Channel depth: enough that `submit` rarely blocks the UI thread, without
+/// letting a fast page-turn spree queue up unbounded stale work.
+const QUEUE_DEPTH: usize = 16;
+
+struct PageJob {
+    page: usize,
+    generation: usize,
+    filenames: Arc<Vec<String>>,
+    archive: Arc<Mutex<ImageArchive>>,
+    image_lru: SharedImageCache,
+    loading_pages: Arc<Mutex<HashSet<usize>>>,
+    page_errors: Arc<Mutex<HashMap<usize, String>>>,
+    max_dimension: Option<u32>,
+}
+
+/// A bounded pool of page-decode worker threads, plus the generation/desired
+/// state `preload_images` uses to keep it pointed at the pages actually
+/// worth decoding.
+pub struct PageDecodeWorkerPool {
+    job_tx: SyncSender<PageJob>,
+    _workers: Vec<thread::JoinHandle<()>>,
+    /// Bumped whenever the reader's position changes enough that in-flight
+    /// jobs for the old position are no longer worth keeping. Workers check
+    /// this before and after the archive read, so a job that goes stale
+    /// mid-flight is dropped instead of landing in the cache.
+    generation: Arc<AtomicUsize>,
+    /// The page indices `preload_images` currently wants decoded. A worker
+    /// drops its job if its page falls out of this set while the job was
+    /// queued or in flight, e.g. after a fast page-turn moved the preload
+    /// window on before the job was reached.
+    desired: Arc<Mutex<HashSet<usize>>>,
+}
+
+impl PageDecodeWorkerPool {
+    pub fn new() -> Self {
+        let (job_tx, job_rx) = mpsc::sync_channel::<PageJob>(QUEUE_DEPTH);
+        let job_rx = Arc::new(Mutex::new(job_rx));
+        let generation = Arc::new(AtomicUsize::new(0));
+        let desired: Arc<Mutex<HashSet<usize>>> = Arc::new(Mutex::new(HashSet::new()));
+        // Archive reads go through `tokio::task::spawn_blocking` internally
+        // (every backend but `FolderImageArchive`), which requires the
+        // calling thread to be inside a Tokio runtime. These workers are
+        // plain OS threads with no runtime context of their own, so capture
+        // the handle of whichever runtime is driving `PageDecodeWorkerPool::new`
+        // (always called from inside `#[tokio::main]`) and enter it per job
+        // instead of using `futures::executor::block_on`.
+        let handle = tokio::runtime::Handle::current();
+
+        let workers = (0..WORKER_COUNT)
+            .map(|i| {
+                let job_rx = job_rx.clone();
+                let generation = generation.clone();
+                let desired = desired.clone();
+                let handle = handle.clone();
+                thread::Builder::new()
+                    .name(format!("page-decode-{i}"))
+                    .spawn(move || worker_loop(job_rx, generation, desired, handle))
+                    .expect("failed to spawn page decode worker")
+            })
+            .collect();
+
+        Self {
+            job_tx,
+            _workers: workers,
+            generation,
+            desired,
+        }
+    }
+
+    /// Invalidate every job currently queued or in flight. Called when the
+    /// active page changes (`CBZViewerApp::on_page_changed`) or a document
+    /// is backgrounded (`CBZViewerApp::take_active_as_tab`), replacing the
+    /// old `preload_tasks` abort sweep.
+    pub fn bump_generation(&self) {
+        self.generation.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn current_generation(&self) -> usize {
+        self.generation.load(Ordering::SeqCst)
+    }
+
+    /// Replace the set of pages worth decoding. `submit` only enqueues pages
+    /// in this set, and workers re-check it after the (potentially slow)
+    /// archive read, so pages that scroll out of the preload window before
+    /// their job is reached are skipped rather than decoded and thrown away.
+    pub fn set_desired(&self, pages: HashSet<usize>) {
+        *self.desired.lock().unwrap() = pages;
+    }
+
+    /// Queue `page` for decode if it isn't already loading. Drops the job
+    /// silently (logging at debug) if the queue is full, since a full
+    /// preload queue means there's already more prefetch work in flight
+    /// than the pool can keep up with and this page will be resubmitted on
+    /// a later frame if it's still wanted.
+    #[allow(clippy::too_many_arguments)]
+    pub fn submit(
+        &self,
+        page: usize,
+        filenames: Arc<Vec<String>>,
+        archive: Arc<Mutex<ImageArchive>>,
+        image_lru: SharedImageCache,
+        loading_pages: Arc<Mutex<HashSet<usize>>>,
+        page_errors: Arc<Mutex<HashMap<usize, String>>>,
+        max_dimension: Option<u32>,
+    ) {
+        {
+            let mut loading = loading_pages.lock().unwrap();
+            if loading.contains(&page) {
+                return;
+            }
+            loading.insert(page);
+        }
+
+        let job = PageJob {
+            page,
+            generation: self.current_generation(),
+            filenames,
+            archive,
+            image_lru,
+            loading_pages: loading_pages.clone(),
+            page_errors,
+            max_dimension,
+        };
+
+        if self.job_tx.try_send(job).is_err() {
+            debug!("Page decode queue full; dropping preload of page {}", page);
+            loading_pages.lock().unwrap().remove(&page);
+        }
+    }
+}
+
+impl Default for PageDecodeWorkerPool {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+fn worker_loop(
+    job_rx: Arc<Mutex<Receiver<PageJob>>>,
+    generation: Arc<AtomicUsize>,
+    desired: Arc<Mutex<HashSet<usize>>>,
+    handle: tokio::runtime::Handle,
+) {
+    loop {
+        let job = {
+            let rx = job_rx.lock().unwrap();
+            rx.recv()
+        };
+        let Ok(job) = job else { break };
+        decode_job(job, &generation, &desired, &handle);
+    }
+}
+
+/// Pure staleness check, split out from `PageJob` so it's testable without
+/// standing up a real `ImageArchive` to populate the rest of the job.
+fn is_stale_job(
+    job_generation: usize,
+    job_page: usize,
+    generation: &AtomicUsize,
+    desired: &Mutex<HashSet<usize>>,
+) -> bool {
+    job_generation != generation.load(Ordering::SeqCst) || !desired.lock().unwrap().contains(&job_page)
+}
+
+fn is_stale(job: &PageJob, generation: &AtomicUsize, desired: &Mutex<HashSet<usize>>) -> bool {
+    is_stale_job(job.generation, job.page, generation, desired)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fresh_job_in_current_generation_and_desired_set_is_not_stale() {
+        let generation = AtomicUsize::new(0);
+        let desired = Mutex::new(HashSet::from([3, 5]));
+        assert!(!is_stale_job(0, 3, &generation, &desired));
+    }
+
+    #[test]
+    fn job_from_an_older_generation_is_stale() {
+        let generation = AtomicUsize::new(2);
+        let desired = Mutex::new(HashSet::from([3]));
+        assert!(is_stale_job(1, 3, &generation, &desired));
+    }
+
+    #[test]
+    fn job_whose_page_scrolled_out_of_the_desired_set_is_stale() {
+        let generation = AtomicUsize::new(0);
+        let desired = Mutex::new(HashSet::from([5, 6]));
+        assert!(is_stale_job(0, 3, &generation, &desired));
+    }
+}
+
+fn decode_job(
+    job: PageJob,
+    generation: &AtomicUsize,
+    desired: &Mutex<HashSet<usize>>,
+    handle: &tokio::runtime::Handle,
+) {
+    if is_stale(&job, generation, desired) {
+        job.loading_pages.lock().unwrap().remove(&job.page);
+        return;
+    }
+
+    let already_sufficient = match job.image_lru.lock().unwrap().get(&job.page) {
+        Some(entry) => job.max_dimension.is_some() || !entry.downsampled,
+        None => false,
+    };
+    if already_sufficient {
+        job.loading_pages.lock().unwrap().remove(&job.page);
+        return;
+    }
+
+    let filename = job.filenames[job.page].clone();
+
+    let page_cache_key = {
+        let archive_path = job.archive.lock().unwrap().path.clone();
+        let mtime = std::fs::metadata(&archive_path)
+            .and_then(|m| m.modified())
+            .ok();
+        comic_archive::page_cache::key(&archive_path, &filename, mtime, job.max_dimension)
+    };
+    if let Some((width, height, rgba)) = comic_archive::page_cache::read(&page_cache_key) {
+        if is_stale(&job, generation, desired) {
+            job.loading_pages.lock().unwrap().remove(&job.page);
+            return;
+        }
+        if let Some(img) =
+            image::RgbaImage::from_raw(width, height, rgba).map(DynamicImage::ImageRgba8)
+        {
+            let loaded_page = LoadedPage {
+                image: PageImage::Static(img),
+                index: job.page,
+                filename: filename.clone(),
+                downsampled: job.max_dimension.is_some(),
+            };
+            job.image_lru.lock().unwrap().put(job.page, loaded_page);
+            job.loading_pages.lock().unwrap().remove(&job.page);
+            job.page_errors.lock().unwrap().remove(&job.page);
+            debug!(
+                "Loaded preloaded page {} from on-disk page cache",
+                job.page
+            );
+            return;
+        }
+    }
+
+    // The archive read can be slow (remote/web archives, large rar/7z
+    // volumes); re-check staleness once it's done so a page that fell out
+    // of the preload window while this read was in flight isn't decoded
+    // and cached for nothing.
+    let buf = {
+        let mut archive = job.archive.lock().unwrap();
+        handle.block_on(archive.read_image_by_index(job.page))
+    };
+    let buf = match buf {
+        Ok(buf) => buf,
+        Err(e) => {
+            job.loading_pages.lock().unwrap().remove(&job.page);
+            job.page_errors
+                .lock()
+                .unwrap()
+                .insert(job.page, e.to_string());
+            debug!("Failed to read preloaded image {}: {:?}", job.page, e);
+            return;
+        }
+    };
+
+    if is_stale(&job, generation, desired) {
+        job.loading_pages.lock().unwrap().remove(&job.page);
+        return;
+    }
+
+    let (image, downsampled) =
+        match crate::cache::image_cache::decode_loaded_page(&filename, &buf, job.max_dimension) {
+            Ok(result) => result,
+            Err(e) => {
+                job.loading_pages.lock().unwrap().remove(&job.page);
+                job.page_errors
+                    .lock()
+                    .unwrap()
+                    .insert(job.page, e.to_string());
+                debug!("Failed to decode preloaded image {}: {:?}", job.page, e);
+                return;
+            }
+        };
+
+    if let PageImage::Static(img) = &image {
+        let rgba = img.to_rgba8();
+        let (w, h) = rgba.dimensions();
+        comic_archive::page_cache::write(&page_cache_key, w, h, rgba.as_raw());
+    }
+
+    let loaded_page = LoadedPage {
+        image,
+        index: job.page,
+        filename,
+        downsampled,
+    };
+
+    job.image_lru.lock().unwrap().put(job.page, loaded_page);
+    job.loading_pages.lock().unwrap().remove(&job.page);
+    job.page_errors.lock().unwrap().remove(&job.page);
+    debug!("Loaded preloaded page {} into LRU cache", job.page);
+}
@@ -1,80 +1,501 @@
-//! Texture cache for egui.
+//! Texture cache for egui: a bounded LRU of uploaded page textures keyed by
+//! page index and zoom, backed by a small worker pool that speculatively
+//! converts upcoming pages into `ColorImage`s ahead of time.
+//!
+//! Previously this cache retained only the single/dual page currently on
+//! screen, so every navigation step threw away the neighbor textures and
+//! forced a synchronous decode + upload on the UI thread, stalling the
+//! frame. The LRU map keeps a radius of neighbors warm instead, and
+//! `prefetch` hands the decode work for those neighbors to background
+//! threads; the UI thread only ever uploads already-decoded `ColorImage`s.
+//!
+//! Decoding here means converting an already-decoded `LoadedPage` (from
+//! `image_lru`) into an `egui::ColorImage` — the actual archive read and
+//! image decode is `image_lru`'s job (see `cache::image_cache`). Reusing
+//! that cache avoids a second archive-decode pipeline here.
 
 use crate::prelude::*;
 use std::collections::HashMap;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
 
-/// Key for a cached texture (page index and zoom).
-#[derive(Clone, PartialEq)]
+/// Number of worker threads converting prefetched pages to `ColorImage`s.
+const WORKER_COUNT: usize = 2;
+
+/// Key for a cached texture: page index plus zoom, quantized to 3 decimal
+/// places so minor float jitter while dragging the zoom slider doesn't
+/// fragment the cache into near-duplicate entries.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
 pub struct TextureKey {
     pub page_idx: usize,
-    pub zoom: f32,
+    zoom_bits: u32,
+}
+
+impl TextureKey {
+    pub fn new(page_idx: usize, zoom: f32) -> Self {
+        Self {
+            page_idx,
+            zoom_bits: quantize_zoom(zoom),
+        }
+    }
 }
 
-/// A cached page texture.
-pub struct PageTexture {
-    pub key: TextureKey,
-    pub handle: TextureHandle,
+fn quantize_zoom(zoom: f32) -> u32 {
+    (zoom * 1000.0).round() as u32 // quantize to 3 decimal places
 }
 
-/// Texture cache for single and dual page modes.
+/// A page handed to a worker thread for `ColorImage` conversion.
+struct DecodeJob {
+    key: TextureKey,
+    generation: usize,
+    page: LoadedPage,
+    zoom: f32,
+}
+
+/// A finished conversion, handed back to the UI thread for the actual
+/// `ctx.load_texture` upload.
+struct Decoded {
+    key: TextureKey,
+    generation: usize,
+    color_image: egui::ColorImage,
+}
+
+/// Bounded LRU cache of uploaded page textures, plus a worker pool that
+/// prefetches neighboring pages around the one currently on screen.
 pub struct TextureCache {
-    pub single: Option<PageTexture>,
-    pub dual: Option<(PageTexture, Option<PageTexture>)>,
-    pub animated: HashMap<String, TextureHandle>, // Add this line
+    entries: LruCache<TextureKey, TextureHandle>,
+    budget_bytes: usize,
+    used_bytes: usize,
+    /// Keys with a decode job in flight, so `prefetch` doesn't queue the
+    /// same page twice while it's being converted.
+    pending: HashMap<TextureKey, usize>,
+    job_tx: Sender<DecodeJob>,
+    result_rx: Receiver<Decoded>,
+    _workers: Vec<thread::JoinHandle<()>>,
+    /// Bumped on `clear()` so decode jobs in flight for a document/zoom that
+    /// no longer applies are discarded instead of uploaded.
+    generation: Arc<AtomicUsize>,
+    /// Per-frame animation textures, uploaded once and reused on every
+    /// subsequent paint instead of re-uploading the same frame every
+    /// repaint. Keyed by `(page_idx, frame_idx)` so a page's whole
+    /// animation can be dropped in one sweep via `evict_animated_page`.
+    pub animated: HashMap<(usize, usize), TextureHandle>,
+    /// Tiles for pages too large to upload as a single texture (see
+    /// `ui::image::draw_static_page_at_rect`), keyed by page/zoom plus tile
+    /// column and row.
+    tiled: HashMap<(TextureKey, u32, u32), TextureHandle>,
 }
 
 impl TextureCache {
     pub fn new() -> Self {
+        Self::with_budget(TEXTURE_CACHE_BUDGET_BYTES)
+    }
+
+    fn with_budget(budget_bytes: usize) -> Self {
         debug!("TextureCache created");
+        let (job_tx, job_rx) = mpsc::channel::<DecodeJob>();
+        let (result_tx, result_rx) = mpsc::channel::<Decoded>();
+        let job_rx = Arc::new(Mutex::new(job_rx));
+
+        let workers = (0..WORKER_COUNT)
+            .map(|i| {
+                let job_rx = job_rx.clone();
+                let result_tx = result_tx.clone();
+                thread::Builder::new()
+                    .name(format!("texture-decode-{i}"))
+                    .spawn(move || {
+                        loop {
+                            let job = {
+                                let rx = job_rx.lock().unwrap();
+                                rx.recv()
+                            };
+                            let Ok(job) = job else { break };
+                            let color_image = to_color_image(&job.page, job.zoom);
+                            let _ = result_tx.send(Decoded {
+                                key: job.key,
+                                generation: job.generation,
+                                color_image,
+                            });
+                        }
+                    })
+                    .expect("failed to spawn texture decode worker")
+            })
+            .collect();
+
         Self {
-            single: None,
-            dual: None,
-            animated: HashMap::new(), // Initialize the new field
+            entries: LruCache::unbounded(),
+            budget_bytes,
+            used_bytes: 0,
+            pending: HashMap::new(),
+            job_tx,
+            result_rx,
+            _workers: workers,
+            generation: Arc::new(AtomicUsize::new(0)),
+            animated: HashMap::new(),
+            tiled: HashMap::new(),
+        }
+    }
+
+    /// Queue decode jobs for the `radius` pages around `around` (in
+    /// `direction`'s favor when budget-limited), skipping pages already
+    /// cached, already pending, or not yet in `image_lru`.
+    pub fn prefetch(
+        &mut self,
+        around: usize,
+        total_pages: usize,
+        radius: usize,
+        zoom: f32,
+        image_lru: &SharedImageCache,
+    ) {
+        let generation = self.generation.load(Ordering::SeqCst);
+        for offset in 0..=radius {
+            for page_idx in [around.checked_sub(offset), Some(around + offset)] {
+                let Some(page_idx) = page_idx else { continue };
+                if page_idx >= total_pages {
+                    continue;
+                }
+                let key = TextureKey::new(page_idx, zoom);
+                if self.entries.contains(&key) || self.pending.contains_key(&key) {
+                    continue;
+                }
+                let Some(page) = image_lru.lock().unwrap().get(&page_idx).cloned() else {
+                    continue; // not decoded yet; retry next frame
+                };
+                if !matches!(page.image, PageImage::Static(_)) {
+                    continue; // animated pages upload their own frames on demand
+                }
+                self.pending.insert(key, generation);
+                let _ = self.job_tx.send(DecodeJob {
+                    key,
+                    generation,
+                    page,
+                    zoom,
+                });
+            }
         }
     }
 
-    pub fn get_single(&self, page_idx: usize, zoom: f32) -> Option<&TextureHandle> {
-        if let Some(pt) = &self.single {
-            if pt.key.page_idx == page_idx && (pt.key.zoom - zoom).abs() < f32::EPSILON {
-                debug!("TextureCache hit: single page {} @ zoom {}", page_idx, zoom);
-                return Some(&pt.handle);
+    /// Upload any `ColorImage`s finished by the worker pool since the last
+    /// call. Must be called from the UI thread once per frame.
+    pub fn poll_ready(&mut self, ctx: &egui::Context) {
+        while let Ok(decoded) = self.result_rx.try_recv() {
+            self.pending.remove(&decoded.key);
+            if decoded.generation != self.generation.load(Ordering::SeqCst) {
+                continue; // stale: document or zoom moved on before this finished
             }
+            let handle = ctx.load_texture(
+                format!("tex{}_{}", decoded.key.page_idx, decoded.key.zoom_bits),
+                decoded.color_image,
+                egui::TextureOptions::default(),
+            );
+            self.insert(decoded.key, handle);
         }
-        debug!(
-            "TextureCache miss: single page {} @ zoom {}",
-            page_idx, zoom
-        );
-        None
+    }
+
+    fn insert(&mut self, key: TextureKey, handle: TextureHandle) {
+        let bytes = texture_bytes(&handle);
+        if let Some(old) = self.entries.put(key, handle) {
+            self.used_bytes = self.used_bytes.saturating_sub(texture_bytes(&old));
+        }
+        self.used_bytes += bytes;
+
+        while self.used_bytes > self.budget_bytes && self.entries.len() > 1 {
+            if let Some((_, evicted)) = self.entries.pop_lru() {
+                self.used_bytes = self.used_bytes.saturating_sub(texture_bytes(&evicted));
+            } else {
+                break;
+            }
+        }
+    }
+
+    pub fn get_single(&mut self, page_idx: usize, zoom: f32) -> Option<&TextureHandle> {
+        let key = TextureKey::new(page_idx, zoom);
+        if self.entries.get(&key).is_some() {
+            debug!("TextureCache hit: single page {} @ zoom {}", page_idx, zoom);
+        } else {
+            debug!(
+                "TextureCache miss: single page {} @ zoom {}",
+                page_idx, zoom
+            );
+        }
+        self.entries.get(&key)
     }
 
     pub fn set_single(&mut self, page_idx: usize, zoom: f32, handle: TextureHandle) {
         debug!("TextureCache set: single page {} @ zoom {}", page_idx, zoom);
-        self.single = Some(PageTexture {
-            key: TextureKey { page_idx, zoom },
-            handle,
+        self.insert(TextureKey::new(page_idx, zoom), handle);
+    }
+
+    /// Cache hits for a dual-page spread, each looked up (and cloned, since
+    /// `TextureHandle` is a cheap `Arc` clone) independently against the
+    /// same map `get_single`/`set_single` use.
+    pub fn get_dual(
+        &mut self,
+        left: usize,
+        right: Option<usize>,
+        zoom: f32,
+    ) -> (Option<TextureHandle>, Option<TextureHandle>) {
+        let left = self.get_single(left, zoom).cloned();
+        let right = right.and_then(|r| self.get_single(r, zoom).cloned());
+        (left, right)
+    }
+
+    /// Number of textures currently cached.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Approximate GPU memory held by cached textures, in bytes.
+    pub fn used_bytes(&self) -> usize {
+        self.used_bytes
+    }
+
+    /// The configured memory budget, in bytes.
+    pub fn budget_bytes(&self) -> usize {
+        self.budget_bytes
+    }
+
+    /// Change the memory budget, evicting least-recently-used entries
+    /// immediately if the new budget is smaller than what's currently
+    /// resident. Lets low-VRAM machines cap texture memory from the debug
+    /// menu instead of only at startup via `TEXTURE_CACHE_BUDGET_BYTES`.
+    pub fn set_budget_bytes(&mut self, budget_bytes: usize) {
+        self.budget_bytes = budget_bytes;
+        while self.used_bytes > self.budget_bytes && self.entries.len() > 1 {
+            if let Some((_, evicted)) = self.entries.pop_lru() {
+                self.used_bytes = self.used_bytes.saturating_sub(texture_bytes(&evicted));
+            }
+        }
+    }
+
+    /// Look up a single already-uploaded animation frame's texture. Actual
+    /// frame advance (elapsed time -> active frame index) lives in
+    /// `ui::image::draw_gif_at_rect`, which calls this once per repaint with
+    /// whichever `frame_idx` its timeline math picked.
+    pub fn get_animated(&self, page_idx: usize, frame_idx: usize) -> Option<&TextureHandle> {
+        self.animated.get(&(page_idx, frame_idx))
+    }
+
+    pub fn set_animated(&mut self, page_idx: usize, frame_idx: usize, handle: TextureHandle) {
+        self.animated.insert((page_idx, frame_idx), handle);
+    }
+
+    /// Drop every cached frame belonging to `page_idx`'s animation, e.g.
+    /// when that page scrolls out of view. Leaves the static-page LRU (and
+    /// any other page's animation frames) untouched, unlike `clear()`.
+    pub fn evict_animated_page(&mut self, page_idx: usize) {
+        self.animated.retain(|key, _| key.0 != page_idx);
+    }
+
+    /// Drop `page_idx`'s cached animation frames that have fallen outside
+    /// the `ring_size`-wide window around `center_idx` (the frame about to
+    /// be shown). Animation frames are read back from the page's scratch
+    /// file on demand (see `cache::image_cache::read_scratch_frame`), so
+    /// unlike a static page's texture there's no reason to keep more than a
+    /// handful of them uploaded at once regardless of how long the
+    /// animation runs.
+    pub fn evict_animated_frames_outside_ring(
+        &mut self,
+        page_idx: usize,
+        center_idx: usize,
+        ring_size: usize,
+    ) {
+        self.animated.retain(|key, _| {
+            key.0 != page_idx || key.1.abs_diff(center_idx) <= ring_size
         });
     }
 
-    fn quantize_zoom(zoom: f32) -> u32 {
-        (zoom * 1000.0).round() as u32 // quantize to 3 decimal places
+    /// Drop every cached animation frame outside `[first, last]`. Used by
+    /// the continuous-scroll view, where several pages are on screen at
+    /// once and `current_page` tracks whichever is centered rather than
+    /// advancing one at a time, so `evict_animated_page` alone would never
+    /// fire for pages that scroll out of the overscan window.
+    pub fn evict_animated_outside(&mut self, first: usize, last: usize) {
+        self.animated.retain(|key, _| key.0 >= first && key.0 <= last);
     }
 
-    /*
-    /// Get cached animated GIF frame texture by key.
-    pub fn get_animated(&self, key: &str) -> Option<&TextureHandle> {
-        self.animated.get(key)
+    /// Drop every static-page texture (and its tiles) outside `[first,
+    /// last]`. The budget-by-bytes eviction in `insert` already bounds
+    /// memory eventually, but in continuous-scroll mode pages scroll past
+    /// in a strict top-to-bottom order, so proactively dropping whatever
+    /// has scrolled far above the viewport keeps GPU memory tight without
+    /// waiting for the budget ceiling to be hit.
+    pub fn evict_outside(&mut self, first: usize, last: usize) {
+        let stale: Vec<TextureKey> = self
+            .entries
+            .iter()
+            .filter(|(key, _)| key.page_idx < first || key.page_idx > last)
+            .map(|(key, _)| *key)
+            .collect();
+        for key in stale {
+            if let Some(handle) = self.entries.pop(&key) {
+                self.used_bytes = self.used_bytes.saturating_sub(texture_bytes(&handle));
+            }
+        }
+        self.tiled
+            .retain(|(key, _, _), _| key.page_idx >= first && key.page_idx <= last);
     }
 
-    /// Set cached animated GIF frame texture by key.
-    pub fn set_animated(&mut self, key: String, handle: TextureHandle) {
-        self.animated.insert(key, handle);
+    /// Drop only the entries whose zoom no longer matches `zoom`, leaving
+    /// up-to-date entries (and animated textures, which aren't zoom-keyed)
+    /// untouched. Used on every zoom-wheel tick, where a full `clear()`
+    /// would also throw away the page(s) already on screen just to
+    /// immediately re-decode them at the same zoom.
+    pub fn invalidate_zoom(&mut self, zoom: f32) {
+        let current = quantize_zoom(zoom);
+        let stale: Vec<TextureKey> = self
+            .entries
+            .iter()
+            .filter(|(key, _)| key.zoom_bits != current)
+            .map(|(key, _)| *key)
+            .collect();
+        for key in stale {
+            if let Some(handle) = self.entries.pop(&key) {
+                self.used_bytes = self.used_bytes.saturating_sub(texture_bytes(&handle));
+            }
+        }
+        self.pending.retain(|key, _| key.zoom_bits == current);
+        self.tiled.retain(|(key, _, _), _| key.zoom_bits == current);
     }
-    */
 
+    /// Look up a cached tile for a page too large to upload as one texture.
+    pub fn get_tile(&self, key: TextureKey, tile_x: u32, tile_y: u32) -> Option<&TextureHandle> {
+        self.tiled.get(&(key, tile_x, tile_y))
+    }
+
+    pub fn set_tile(&mut self, key: TextureKey, tile_x: u32, tile_y: u32, handle: TextureHandle) {
+        self.tiled.insert((key, tile_x, tile_y), handle);
+    }
+
+    /// Drop all cached textures and cancel in-flight decode jobs (by
+    /// bumping `generation`, so results already queued are discarded
+    /// instead of uploaded) ahead of a document switch or zoom reset.
     pub fn clear(&mut self) {
         debug!("TextureCache cleared");
-        self.single = None;
-        self.dual = None;
-        self.animated.clear(); // Clear animated cache as well
+        self.entries.clear();
+        self.used_bytes = 0;
+        self.pending.clear();
+        self.animated.clear();
+        self.tiled.clear();
+        self.generation.fetch_add(1, Ordering::SeqCst);
+        while self.result_rx.try_recv().is_ok() {}
+    }
+}
+
+/// Approximate GPU-resident size of a texture, as if it were RGBA8.
+fn texture_bytes(handle: &TextureHandle) -> usize {
+    let [w, h] = handle.size();
+    w * h * 4
+}
+
+/// Bounded LRU cache of uploaded thumbnail-grid textures, keyed by page
+/// index and frame index (frame 0 for static thumbnails, the hovered
+/// frame otherwise, so an animated thumbnail's frames upload once each
+/// instead of being re-uploaded every time hover advances them). Mirrors
+/// `TextureCache`'s budget-by-bytes eviction, but much smaller and without
+/// the decode worker pool since thumbnails are decoded by
+/// `display_thumbnail_grid`'s own spawned tasks.
+pub struct ThumbnailTextureCache {
+    entries: LruCache<(usize, usize), TextureHandle>,
+    budget_bytes: usize,
+    used_bytes: usize,
+}
+
+impl ThumbnailTextureCache {
+    pub fn new() -> Self {
+        Self {
+            entries: LruCache::unbounded(),
+            budget_bytes: THUMBNAIL_TEXTURE_CACHE_BUDGET_BYTES,
+            used_bytes: 0,
+        }
+    }
+
+    pub fn get_frame(&mut self, page_idx: usize, frame_idx: usize) -> Option<&TextureHandle> {
+        self.entries.get(&(page_idx, frame_idx))
+    }
+
+    pub fn set_frame(&mut self, page_idx: usize, frame_idx: usize, handle: TextureHandle) {
+        let bytes = texture_bytes(&handle);
+        if let Some(old) = self.entries.put((page_idx, frame_idx), handle) {
+            self.used_bytes = self.used_bytes.saturating_sub(texture_bytes(&old));
+        }
+        self.used_bytes += bytes;
+
+        while self.used_bytes > self.budget_bytes && self.entries.len() > 1 {
+            if let Some((_, evicted)) = self.entries.pop_lru() {
+                self.used_bytes = self.used_bytes.saturating_sub(texture_bytes(&evicted));
+            } else {
+                break;
+            }
+        }
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.used_bytes = 0;
+    }
+}
+
+impl Default for ThumbnailTextureCache {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Below this fraction of native size, resample with a proper filter
+/// instead of uploading the full-resolution bitmap and letting the GPU
+/// minify it every frame, which is both wasteful (VRAM, upload bandwidth)
+/// and visibly aliased on large scans viewed zoomed out.
+const DOWNSCALE_ZOOM_THRESHOLD: f32 = 0.75;
+
+/// Build the `ColorImage` to upload for a static page displayed at `zoom`,
+/// pre-downscaling with Lanczos3 when `zoom` is meaningfully below native
+/// size. Shared by the background prefetch path (`to_color_image`) and the
+/// synchronous single/dual-page draw paths so both benefit identically.
+pub(crate) fn color_image_for_zoom(img: &DynamicImage, zoom: f32) -> egui::ColorImage {
+    let display_img = display_image_for_zoom(img, zoom);
+    let (w, h) = display_img.dimensions();
+    egui::ColorImage::from_rgba_unmultiplied([w as usize, h as usize], &display_img.to_rgba8())
+}
+
+/// The bitmap actually worth uploading for `img` displayed at `zoom`:
+/// downscaled to roughly its on-screen pixel size below
+/// `DOWNSCALE_ZOOM_THRESHOLD`, or the native image otherwise. Exposed
+/// separately from `color_image_for_zoom` so callers that may need to tile
+/// the result (see `ui::image::draw_static_page_at_rect`) can inspect its
+/// dimensions before committing to a single texture.
+///
+/// This resizes on demand per zoom bucket rather than precomputing a fixed
+/// mip pyramid: `TextureCache` already keys textures by quantized zoom and
+/// evicts stale-zoom entries (see `invalidate_zoom`) instead of clearing
+/// everything on every tick, which bounds VRAM the same way a pyramid would
+/// without the upfront cost of building levels a page may never be viewed
+/// at.
+pub(crate) fn display_image_for_zoom(img: &DynamicImage, zoom: f32) -> DynamicImage {
+    if zoom < DOWNSCALE_ZOOM_THRESHOLD {
+        let (w, h) = img.dimensions();
+        let target_w = ((w as f32 * zoom).round() as u32).max(1);
+        let target_h = ((h as f32 * zoom).round() as u32).max(1);
+        img.resize(target_w, target_h, image::imageops::FilterType::Lanczos3)
+    } else {
+        img.clone()
+    }
+}
+
+/// Convert a decoded static page into an `egui::ColorImage`, ready for
+/// `ctx.load_texture` on the UI thread.
+fn to_color_image(page: &LoadedPage, zoom: f32) -> egui::ColorImage {
+    match &page.image {
+        PageImage::Static(img) => color_image_for_zoom(img, zoom),
+        // Animated pages read their frames back from a scratch file on
+        // demand (see `ui::image::draw_gif_at_rect`), and vector pages
+        // rasterize themselves on demand in `ui::image::draw_vector_page`;
+        // callers filter both out before queuing a decode job here.
+        PageImage::Animated { .. } => {
+            egui::ColorImage::new([1, 1], egui::Color32::TRANSPARENT)
+        }
+        #[cfg(feature = "svg")]
+        PageImage::Vector(_) => egui::ColorImage::new([1, 1], egui::Color32::TRANSPARENT),
     }
 }
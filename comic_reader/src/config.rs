@@ -1,12 +1,33 @@
 //! Application-wide configuration constants.
 
+use std::path::PathBuf;
+
 pub const NAME: &str = concat!("Comic Reader ", env!("CARGO_PKG_VERSION"));
 /// Default window width.
 pub const WIN_WIDTH: f32 = 720.0;
 /// Default window height.
 pub const WIN_HEIGHT: f32 = 1080.0;
-/// Number of images to keep in cache.
-pub const CACHE_SIZE: usize = 20;
+/// Approximate decoded-pixel memory budget for `image_lru`, in bytes.
+/// The cache evicts least-recently-used pages once the sum of their
+/// decoded sizes (RGBA8, width * height * 4) crosses this, rather than
+/// capping the number of entries.
+pub const CACHE_MEMORY_BUDGET_BYTES: usize = 256 * 1024 * 1024;
+/// Maximum width/height a preloaded (read-ahead) page is downsampled to
+/// before it enters `image_lru`. Keeps large scanned pages from blowing
+/// the memory budget on their own; the actively displayed page and
+/// "save image" always fetch at full resolution regardless of this cap.
+pub const MAX_PRELOAD_DIMENSION: u32 = 2200;
+/// How far over the viewport's pixel size the actively displayed page is
+/// decoded, so a small amount of pinch-zoom or window resize doesn't
+/// immediately soften the page before the next full-resolution re-decode
+/// catches up (see `CBZViewerApp::active_page_max_dimension`).
+pub const ACTIVE_PAGE_OVERSAMPLE_FACTOR: f32 = 1.5;
+/// When `cap_dimension` shrinks a page to less than this fraction of its
+/// original size, it switches from the cheap `Triangle` filter to the
+/// sharper (and pricier) `Lanczos3`: a page shrunk that much is usually the
+/// actively viewed one being fit to a small viewport, where the quality
+/// difference is visible, rather than a read-ahead page getting a mild trim.
+pub const DOWNSCALE_QUALITY_THRESHOLD: f32 = 0.5;
 /// Border size for image display.
 // pub const BORDER_SIZE: f32 = 100.0;
 /// Margin between pages in dual mode.
@@ -15,8 +36,109 @@ pub const PAGE_MARGIN_SIZE: usize = 0;
 pub const DEFAULT_DUAL_PAGE_MODE: bool = false;
 /// Default reading direction.
 pub const DEFAULT_RIGHT_TO_LEFT: bool = false;
+/// Default width/height ratio above which a page is treated as a wide
+/// two-page spread scan and rendered solo in dual-page mode instead of
+/// being paired with a neighboring page.
+pub const DEFAULT_SPREAD_ASPECT_THRESHOLD: f32 = 1.0;
 /// Whether reading direction affects arrow keys.
 // pub const READING_DIRECTION_AFFECTS_ARROWS: bool = true;
 /// How many pages ahead to pre-cache.
 pub const READ_AHEAD: usize = 16;
+/// Same as `READ_AHEAD`, but for web archives: each page is a network fetch
+/// rather than a local extract, so a smaller window avoids flooding the
+/// remote host with requests for pages the reader may never reach.
+pub const READ_AHEAD_WEB: usize = 4;
+/// How many pages behind the current one to keep warm, in addition to the
+/// read-ahead window in the opposite direction of travel. Small relative to
+/// `READ_AHEAD` since it only exists to make reversing direction feel
+/// instant, not to mirror the forward window.
+pub const READ_BEHIND: usize = 4;
+/// Approximate GPU memory budget for `TextureCache`, in bytes. Uploaded
+/// page textures are evicted least-recently-used once their summed size
+/// (RGBA8, width * height * 4) crosses this.
+pub const TEXTURE_CACHE_BUDGET_BYTES: usize = 512 * 1024 * 1024;
+/// How many pages on either side of the current one `TextureCache::prefetch`
+/// speculatively decodes ahead of time.
+pub const TEXTURE_PREFETCH_RADIUS: usize = 2;
 pub const LOG_TIMEOUT: usize = 2;
+/// How many entries `UiLogger`'s history ring buffer keeps, regardless of
+/// how long ago the transient toast for each one expired.
+pub const LOG_HISTORY_CAPACITY: usize = 200;
+/// Approximate GPU memory budget for cached thumbnail-grid textures, in
+/// bytes. Much smaller than `TEXTURE_CACHE_BUDGET_BYTES` since thumbnails
+/// are tiny, but a long comic can still have thousands of them on screen
+/// over a scroll session.
+pub const THUMBNAIL_TEXTURE_CACHE_BUDGET_BYTES: usize = 64 * 1024 * 1024;
+/// How many screenfuls beyond the visible viewport `display_thumbnail_grid`
+/// prefetches thumbnails for, in each scroll direction, before scroll
+/// velocity biases the window further toward the direction of travel.
+pub const DEFAULT_THUMB_PREFETCH_SCREENS: f32 = 1.0;
+/// How many decoded animation frames stay resident (as uploaded textures)
+/// at once during GIF/WebP/APNG playback. The rest live in the page's
+/// on-disk scratch file and are streamed back in as playback advances, so
+/// a long animation's memory footprint stays flat regardless of its
+/// total frame count.
+pub const ANIMATION_FRAME_RING_SIZE: usize = 3;
+/// Size and quality used for the first-page thumbnail generated when an
+/// archive is opened, shown next to its entry in the recent-files panel.
+pub const RECENT_THUMB_SIZE: u32 = 96;
+pub const RECENT_THUMB_QUALITY: u8 = 70;
+/// How many recently opened archives `RecentsStore` remembers.
+pub const MAX_RECENTS: usize = 20;
+
+/// Runtime-adjustable settings, serialized to a TOML file in the user's
+/// config directory via `ui::settings_modal`. Values here shadow the
+/// compile-time constants above, which remain as the fallback defaults for
+/// a fresh install.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+pub struct Settings {
+    pub cache_memory_budget_bytes: usize,
+    pub read_ahead: usize,
+    pub read_ahead_web: usize,
+    pub page_margin_size: usize,
+    pub dual_page_mode: bool,
+    pub right_to_left: bool,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Self {
+            cache_memory_budget_bytes: CACHE_MEMORY_BUDGET_BYTES,
+            read_ahead: READ_AHEAD,
+            read_ahead_web: READ_AHEAD_WEB,
+            page_margin_size: PAGE_MARGIN_SIZE,
+            dual_page_mode: DEFAULT_DUAL_PAGE_MODE,
+            right_to_left: DEFAULT_RIGHT_TO_LEFT,
+        }
+    }
+}
+
+fn settings_path() -> PathBuf {
+    dirs::config_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("comic_suite")
+        .join("settings.toml")
+}
+
+impl Settings {
+    /// Load settings from disk, falling back to defaults if missing or
+    /// invalid.
+    pub fn load() -> Self {
+        std::fs::read_to_string(settings_path())
+            .ok()
+            .and_then(|s| toml::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    /// Persist settings to disk, creating the config directory if needed.
+    pub fn save(&self) {
+        let path = settings_path();
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        if let Ok(toml) = toml::to_string_pretty(self) {
+            let _ = std::fs::write(path, toml);
+        }
+    }
+}
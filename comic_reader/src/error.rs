@@ -22,6 +22,24 @@ pub enum AppError {
     IndexOutOfBounds,
     #[error("Image processing error: {0}")]
     ImageProcessingError(String),
+    #[error("AVIF error: {0}")]
+    Avif(String),
+    #[error("HEIF error: {0}")]
+    Heif(String),
+    #[error("Archive is password-protected")]
+    Encrypted,
+    #[error("Archive uses an unsupported or unknown encryption method")]
+    UnknownEncryption,
+    #[error("Archive header is damaged")]
+    ArchiveHeaderDamaged,
+    #[error("File CRC check failed \u{2014} the archive is corrupt")]
+    FileCrcError,
+    #[error("Next volume of this multi-part archive was not found")]
+    NextVolumeNotFound,
+    #[error("Required external tool not found: install {0}")]
+    MissingTool(String),
+    #[error("Entry not found in archive")]
+    EntryNotFound,
     #[error("Unsupported archive type or not found")]
     UnsupportedArchive,
     #[error("Network error: {0}")]
@@ -42,7 +60,16 @@ impl From<ArchiveError> for AppError {
             ArchiveError::Other(e) => AppError::Other(e),
             ArchiveError::Zip(e) => AppError::Zip(e),
             ArchiveError::ImageProcessingError(e) => AppError::ImageProcessingError(e),
-            _ => AppError::Other("Unknown archive error".to_string()),
+            ArchiveError::Avif(e) => AppError::Avif(e),
+            ArchiveError::Heif(e) => AppError::Heif(e),
+            ArchiveError::Encrypted => AppError::Encrypted,
+            ArchiveError::UnknownEncryption => AppError::UnknownEncryption,
+            ArchiveError::ArchiveHeaderDamaged => AppError::ArchiveHeaderDamaged,
+            ArchiveError::FileCrcError => AppError::FileCrcError,
+            ArchiveError::NextVolumeNotFound => AppError::NextVolumeNotFound,
+            ArchiveError::MissingTool(e) => AppError::MissingTool(e),
+            ArchiveError::EntryNotFound => AppError::EntryNotFound,
+            other => AppError::Other(other.to_string()),
         }
     }
 }
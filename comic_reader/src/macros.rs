@@ -1,7 +1,7 @@
 #[macro_export]
 macro_rules! comic_exts {
     () => {{
-        let mut v = vec!["cbz", "zip"];
+        let mut v = vec!["cbz", "zip", "cbt", "tar"];
         #[cfg(feature = "rar")]
         {
             v.push("cbr");
@@ -23,6 +23,7 @@ macro_rules! comic_filters {
         let exts = $crate::comic_exts!();
         dlg = dlg.add_filter("Comic Book Archive", &exts);
         dlg = dlg.add_filter("Comic CBZ", &["cbz", "zip"]);
+        dlg = dlg.add_filter("Comic CBT", &["cbt", "tar"]);
         #[cfg(feature = "rar")]
         {
             dlg = dlg.add_filter("Comic RAR", &["cbr", "rar"]);
@@ -2,50 +2,63 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
 mod app;
+mod bin_provision;
+mod bookmarks;
 mod cache;
 mod config;
 mod error;
 mod macros;
 mod prelude;
+mod recents;
 mod ui;
 mod archive_view;
+mod tabs;
+mod watch;
 
+use crate::bin_provision::{ArchiveKind, ToolSource};
 use crate::prelude::*;
 
-fn check_bin(bin: &str, msg: &str) -> bool {
-    log::info!("Checking for '{}' in PATH...", bin);
-    if which::which(bin).is_err() {
-        rfd::MessageDialog::new()
-            .set_title(&format!("Missing {}", bin))
-            .set_description(&format!(
-                "The '{}' executable was not found in your PATH.\n{}",
-                bin, msg
-            ))
-            .set_buttons(rfd::MessageButtons::Ok)
-            .set_level(rfd::MessageLevel::Error)
-            .show();
-        log::warn!("'{}' not found in PATH. {}", bin, msg);
-        return false;
-    } else {
-        log::info!("'{}' found in PATH.", bin);
+#[cfg(feature = "rar_cli")]
+const UNRAR_SOURCE: ToolSource = ToolSource {
+    tool: "unrar",
+    version: "6.2.12",
+    url: "https://www.rarlab.com/rar/unrarsrc-6.2.12.tar.gz",
+    sha256: "0000000000000000000000000000000000000000000000000000000000000000",
+    entry_path: "unrar",
+    kind: ArchiveKind::TarGz,
+};
+
+#[cfg(feature = "7z")]
+const SEVEN_ZIP_SOURCE: ToolSource = ToolSource {
+    tool: "7z",
+    version: "23.01",
+    url: "https://www.7-zip.org/a/7z2301-linux-x64.tar.xz",
+    sha256: "0000000000000000000000000000000000000000000000000000000000000000",
+    entry_path: "7zzs",
+    kind: ArchiveKind::TarGz,
+};
+
+/// Make sure `source.tool` is usable, fetching and caching it first if it's
+/// missing from PATH, and prepend its cache dir to PATH for this run.
+fn ensure_bin(source: &ToolSource) {
+    if let Some(dir) = bin_provision::ensure_tool_available(source) {
+        bin_provision::prepend_to_path(&dir);
     }
-    true
 }
 
 #[tokio::main]
 async fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // Initialize logging (to file and console)
-    env_logger::Builder::from_default_env()
-        .format_timestamp_secs()
-        .init();
+    // Initialize logging (to file and console, plus an in-app ring buffer
+    // the Debug Info window's log console reads from).
+    ui::log_console::init();
 
     log::info!("Initialising...");
 
-    #[cfg(feature = "rar")]
-    {
-        check_bin("unrar", "RAR archives will not open.");
-        check_bin("rar", "RAR archives will save.");
-    }
+    // The native RAR backend talks to libunrar in-process, so it doesn't
+    // need these binaries; only the CLI fallback (`rar_cli`) shells out to
+    // them.
+    #[cfg(feature = "rar_cli")]
+    ensure_bin(&UNRAR_SOURCE);
     #[cfg(feature = "7z")]
     {
         #[cfg(target_os = "windows")]
@@ -60,13 +73,12 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
                     std::env::set_var("PATH", path_var);
                 }
             } else {
-                check_bin("7z", "7z archives will not open.");
+                ensure_bin(&SEVEN_ZIP_SOURCE);
             }
-            // check_bin("7z", "7z archives will not open.");
         }
         #[cfg(not(target_os = "windows"))]
         {
-            check_bin("7z", "7z archives will not open.");
+            ensure_bin(&SEVEN_ZIP_SOURCE);
         }
     }
 
@@ -1,7 +1,6 @@
 // std
 pub use std::{
-    collections::HashSet,
-    num::NonZeroUsize,
+    collections::{HashMap, HashSet},
     path::PathBuf,
     sync::{Arc, Mutex},
     time::{Duration, Instant},
@@ -22,22 +21,29 @@ pub use lru::LruCache;
 // crate modules
 pub use crate::{
     app::CBZViewerApp,
+    bookmarks::{ArchiveBookmarks, Bookmark, BookmarkStore},
     cache::{
-        SharedImageCache,
+        PageDecodeWorkerPool, SharedImageCache,
         image_cache::{LoadedPage, PageImage},
         load_image_async, new_image_cache,
-        texture_cache::TextureCache,
+        texture_cache::{TextureCache, ThumbnailTextureCache},
     },
     config::*,
     error::AppError,
+    recents::{RecentEntry, RecentsStore},
+    tabs::ComicTab,
     ui::{
         clamp_pan,
         handle_pan,
         handle_zoom,
-        image::{draw_dual_page, draw_single_page, draw_spinner},
-        log::UiLogger,
+        image::{
+            draw_dual_page, draw_page_comment, draw_page_error, draw_single_page, draw_spinner,
+            is_spread_page,
+        },
+        library::LibraryState,
+        log::{UiLogLevel, UiLogger},
         manifest_editor::ManifestEditor,
-        // thumbnail_grid::ThumbnailGrid,
+        thumbnail_grid::{DEFAULT_THUMB_FIT, ThumbFit, ThumbImage},
     },
 };
 pub use comic_archive::prelude::*;
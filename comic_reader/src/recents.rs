@@ -0,0 +1,93 @@
+//! Recently opened archives, persisted across restarts under
+//! `dirs::cache_dir()`, plus the last directory a file-open dialog was
+//! pointed at. Separate from `BookmarkStore`: this is app-level
+//! convenience state (what did I open, and from where), not per-archive
+//! reading progress.
+
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::{Deserialize, Serialize};
+
+use crate::config::MAX_RECENTS;
+
+/// One entry in the recents list.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct RecentEntry {
+    pub path: PathBuf,
+    pub last_opened_unix_secs: u64,
+    /// SHA-256 digest of the first page's raw bytes, set once a thumbnail
+    /// for it has been generated, so the recents panel can look it up in
+    /// `comic_archive::thumbnail_cache` without reopening the archive.
+    pub thumb_digest: Option<String>,
+}
+
+/// The recents list and the last directory a file dialog was opened in.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct RecentsStore {
+    pub entries: Vec<RecentEntry>,
+    pub last_dir: Option<PathBuf>,
+}
+
+fn recents_path() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("comic_suite")
+        .join("recents.toml")
+}
+
+fn now_unix_secs() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+impl RecentsStore {
+    /// Load the store from disk, falling back to empty if missing or invalid.
+    pub fn load() -> Self {
+        std::fs::read_to_string(recents_path())
+            .ok()
+            .and_then(|s| toml::from_str(&s).ok())
+            .unwrap_or_default()
+    }
+
+    /// Persist the store to disk, creating the cache directory if needed.
+    pub fn save(&self) {
+        let path = recents_path();
+        if let Some(dir) = path.parent() {
+            let _ = std::fs::create_dir_all(dir);
+        }
+        if let Ok(toml) = toml::to_string_pretty(self) {
+            let _ = std::fs::write(path, toml);
+        }
+    }
+
+    /// Record that `path` was just opened: moves/creates it at the front
+    /// of the list, remembers its parent directory for the next file
+    /// dialog, drops the oldest entry past `MAX_RECENTS`, and persists.
+    pub fn record_opened(&mut self, path: &Path) {
+        self.entries.retain(|e| e.path != path);
+        self.entries.insert(
+            0,
+            RecentEntry {
+                path: path.to_path_buf(),
+                last_opened_unix_secs: now_unix_secs(),
+                thumb_digest: None,
+            },
+        );
+        self.entries.truncate(MAX_RECENTS);
+        self.last_dir = path.parent().map(Path::to_path_buf);
+        self.save();
+    }
+
+    /// Attach a generated thumbnail's digest to `path`'s entry, if it's
+    /// still in the list, and persist the change.
+    pub fn set_thumb_digest(&mut self, path: &Path, digest: String) {
+        if let Some(entry) = self.entries.iter_mut().find(|e| e.path == path) {
+            entry.thumb_digest = Some(digest);
+            self.save();
+        }
+    }
+}
@@ -20,15 +20,71 @@ impl CBZViewerApp {
                     ui.separator();
                     self.debug_lru_cache(ui);
                     ui.separator();
+                    self.debug_texture_cache(ui);
+                    ui.separator();
                     self.debug_ram_usage(ui);
-                    // ui.separator();
-                    // self.debug_network_usage(ui);
+                    ui.separator();
+                    self.debug_network_usage(ui);
+                    ui.separator();
+                    self.debug_log_console(ui);
                 });
             });
             self.show_debug_menu = show;
         }
     }
 
+    /// Scrollable history of every log message, with level coloring and a
+    /// level filter, so a faded toast can still be reviewed afterwards.
+    pub fn display_log_history(&mut self, ctx: &egui::Context) {
+        if !self.show_log_history {
+            return;
+        }
+        let mut show = self.show_log_history;
+        egui::Window::new(
+            RichText::new("\u{f08a} Log History")
+                .color(Color32::from_rgb(255, 200, 0))
+                .heading(),
+        )
+        .open(&mut show)
+        .resizable(true)
+        .vscroll(false)
+        .show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                ui.label("Show:");
+                ui.checkbox(&mut self.log_filter_info, "Info");
+                ui.checkbox(&mut self.log_filter_warning, "Warning");
+                ui.checkbox(&mut self.log_filter_error, "Error");
+            });
+            ui.separator();
+            egui::ScrollArea::vertical()
+                .auto_shrink([false, false])
+                .stick_to_bottom(true)
+                .show(ui, |ui| {
+                    let logger = self.ui_logger.lock().unwrap();
+                    for entry in &logger.history {
+                        let shown = match entry.level {
+                            UiLogLevel::Info => self.log_filter_info,
+                            UiLogLevel::Warning => self.log_filter_warning,
+                            UiLogLevel::Error => self.log_filter_error,
+                        };
+                        if !shown {
+                            continue;
+                        }
+                        ui.colored_label(
+                            entry.level.color(),
+                            format!(
+                                "[{:>5.1}s ago] {}: {}",
+                                entry.time.elapsed().as_secs_f32(),
+                                entry.level.as_str(),
+                                entry.message
+                            ),
+                        );
+                    }
+                });
+        });
+        self.show_log_history = show;
+    }
+
     fn debug_thumbnail_cache(&self, ui: &mut egui::Ui) {
         ui.collapsing(
             RichText::new("\u{f03e} Thumbnail Cache")
@@ -51,10 +107,11 @@ impl CBZViewerApp {
                         ui.end_row();
 
                         for (k, v) in cache.iter() {
-                            let bytes = v.as_bytes().len();
+                            let bytes = v.approx_bytes();
                             total_thumb_bytes += bytes;
+                            let first = v.first_frame();
                             ui.label(RichText::new(format!("{k}")).color(Color32::YELLOW));
-                            ui.label(format!("{}x{}", v.width(), v.height()));
+                            ui.label(format!("{}x{}", first.width(), first.height()));
                             ui.label(
                                 RichText::new(format!("{}", bytes)).color(Color32::LIGHT_GREEN),
                             );
@@ -88,7 +145,20 @@ impl CBZViewerApp {
                     RichText::new(format!("Entries: {}", image_lru.len()))
                         .color(Color32::LIGHT_BLUE),
                 );
-                let mut total_lru_bytes = 0usize;
+                let budget_bytes = image_lru.budget_bytes();
+                let used_bytes = image_lru.used_bytes();
+                ui.label(
+                    RichText::new(format!(
+                        "\u{f200} Budget: {:.2} / {:.2} MB",
+                        used_bytes as f64 / (1024.0 * 1024.0),
+                        budget_bytes as f64 / (1024.0 * 1024.0)
+                    ))
+                    .color(if used_bytes > budget_bytes {
+                        Color32::LIGHT_RED
+                    } else {
+                        Color32::LIGHT_BLUE
+                    }),
+                );
 
                 egui::Grid::new("lru_cache_grid")
                     .striped(true)
@@ -101,10 +171,14 @@ impl CBZViewerApp {
 
                         for (k, v) in image_lru.iter() {
                             let (w, h) = v.image.dimensions();
-                            let bytes = (w as usize) * (h as usize) * 4; // RGBA8
-                            total_lru_bytes += bytes;
+                            let bytes = v.approx_bytes();
                             ui.label(RichText::new(format!("{k}")).color(Color32::YELLOW));
-                            ui.label(format!("{}x{}", w, h));
+                            ui.label(format!(
+                                "{}x{}{}",
+                                w,
+                                h,
+                                if v.downsampled { " (downsampled)" } else { "" }
+                            ));
                             ui.label(
                                 RichText::new(format!("{}", bytes)).color(Color32::LIGHT_GREEN),
                             );
@@ -117,8 +191,8 @@ impl CBZViewerApp {
                 ui.label(
                     RichText::new(format!(
                         "\u{f1ec} Total: {} bytes ({:.2} MB)",
-                        total_lru_bytes,
-                        total_lru_bytes as f64 / (1024.0 * 1024.0)
+                        used_bytes,
+                        used_bytes as f64 / (1024.0 * 1024.0)
                     ))
                     .color(Color32::from_rgb(0, 200, 0))
                     .strong(),
@@ -127,6 +201,49 @@ impl CBZViewerApp {
         );
     }
 
+    fn debug_texture_cache(&mut self, ui: &mut egui::Ui) {
+        ui.collapsing(
+            RichText::new("\u{f03e} Texture Cache")
+                .color(Color32::from_rgb(0, 255, 180))
+                .strong(),
+            |ui| {
+                let used_bytes = self.texture_cache.used_bytes();
+                let budget_bytes = self.texture_cache.budget_bytes();
+                ui.label(
+                    RichText::new(format!("Entries: {}", self.texture_cache.len()))
+                        .color(Color32::LIGHT_BLUE),
+                );
+                ui.label(
+                    RichText::new(format!(
+                        "\u{f200} Budget: {:.2} / {:.2} MB",
+                        used_bytes as f64 / (1024.0 * 1024.0),
+                        budget_bytes as f64 / (1024.0 * 1024.0)
+                    ))
+                    .color(if used_bytes > budget_bytes {
+                        Color32::LIGHT_RED
+                    } else {
+                        Color32::LIGHT_BLUE
+                    }),
+                );
+
+                // Lets low-VRAM machines cap GPU texture memory below the
+                // `TEXTURE_CACHE_BUDGET_BYTES` default without a rebuild.
+                let mut budget_mb = budget_bytes as f64 / (1024.0 * 1024.0);
+                if ui
+                    .add(
+                        egui::Slider::new(&mut budget_mb, 32.0..=2048.0)
+                            .text("Budget (MB)")
+                            .suffix(" MB"),
+                    )
+                    .changed()
+                {
+                    self.texture_cache
+                        .set_budget_bytes((budget_mb * 1024.0 * 1024.0) as usize);
+                }
+            },
+        );
+    }
+
     fn debug_ram_usage(&self, ui: &mut egui::Ui) {
         ui.heading(
             RichText::new("\u{f5dc} RAM Usage")
@@ -150,52 +267,135 @@ impl CBZViewerApp {
         }
     }
 
+    /// Network activity for the active archive, if it's a `WebImageArchive`.
+    /// `sysinfo`'s interface counters can't be attributed to this process's
+    /// own HTTP requests, so this reads byte/request counters instrumented
+    /// directly into `WebImageArchive` (see `comic_archive::net_stats`)
+    /// instead.
     fn debug_network_usage(&self, ui: &mut egui::Ui) {
-        todo!("Network usage debugging is currently disabled due to sysinfo limitations.");
-        use egui::{Color32, RichText};
-        use sysinfo::System;
-        /*
-            ui.heading(
-                RichText::new("\u{f6ff} Network Usage")
-                    .color(Color32::from_rgb(100, 200, 255))
-                    .strong(),
-            );
+        ui.collapsing(
+            RichText::new("\u{f6ff} Network Usage")
+                .color(Color32::from_rgb(100, 200, 255))
+                .strong(),
+            |ui| {
+                let Some(archive) = &self.archive else {
+                    ui.label(RichText::new("No archive open.").color(Color32::GRAY));
+                    return;
+                };
+                let Some(net_stats) = archive.lock().unwrap().net_stats() else {
+                    ui.label(
+                        RichText::new("Not a web archive \u{2014} nothing to report.")
+                            .color(Color32::GRAY),
+                    );
+                    return;
+                };
+                let mut stats = net_stats.lock().unwrap();
 
-            let mut sys = System::new();
-            sys.refresh_networks();
+                egui::Grid::new("network_grid")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label(RichText::new("\u{f0ac} URL").strong());
+                        ui.label(RichText::new("\u{f019} Bytes").strong());
+                        ui.label(RichText::new("Requests").strong());
+                        ui.label(RichText::new("Cache hits").strong());
+                        ui.end_row();
 
-            let mut total_received = 0u64;
-            let mut total_transmitted = 0u64;
+                        for (url, stat) in stats.per_url.iter() {
+                            ui.label(RichText::new(url).color(Color32::YELLOW));
+                            ui.label(format!("{:.2} MB", stat.bytes as f64 / 1024.0 / 1024.0));
+                            ui.label(format!("{}", stat.requests));
+                            ui.label(format!("{}", stat.cache_hits));
+                            ui.end_row();
+                        }
+                    });
 
-            egui::Grid::new("network_grid")
-                .striped(true)
-                .show(ui, |ui| {
-                    ui.label(RichText::new("\u{f0ac} Interface").strong());
-                    ui.label(RichText::new("\u{f019} Received (MB)").strong());
-                    ui.label(RichText::new("\u{f093} Sent (MB)").strong());
-                    ui.end_row();
-
-                    for (name, data) in sys.networks().iter() {
-                        let received = data.received();
-                        let transmitted = data.transmitted();
-                        total_received += received;
-                        total_transmitted += transmitted;
-                        ui.label(RichText::new(name).color(Color32::YELLOW));
-                        ui.label(format!("{:.2}", received as f64 / 1024.0 / 1024.0));
-                        ui.label(format!("{:.2}", transmitted as f64 / 1024.0 / 1024.0));
-                        ui.end_row();
-                    }
-                });
+                ui.separator();
+                ui.label(
+                    RichText::new(format!(
+                        "Total: \u{f019} {:.2} MB over {} requests ({} cache hits), {:.1} KB/s",
+                        stats.total_bytes as f64 / 1024.0 / 1024.0,
+                        stats.total_requests,
+                        stats.total_cache_hits,
+                        stats.bytes_per_sec() / 1024.0
+                    ))
+                    .color(Color32::from_rgb(0, 200, 255))
+                    .strong(),
+                );
+            },
+        );
+    }
 
-            ui.label(
-                RichText::new(format!(
-                    "Total: \u{f019} {:.2} MB, \u{f093} {:.2} MB",
-                    total_received as f64 / 1024.0 / 1024.0,
-                    total_transmitted as f64 / 1024.0 / 1024.0
-                ))
-                .color(Color32::from_rgb(0, 200, 255))
+    /// Ring-buffer log console: every `log::debug!`/`trace!` call made
+    /// anywhere in the app (see `ui::log_console`), filterable by level
+    /// and substring, so cache/decode instrumentation is visible without
+    /// attaching an external terminal.
+    fn debug_log_console(&mut self, ui: &mut egui::Ui) {
+        ui.collapsing(
+            RichText::new("\u{f120} Log Console")
+                .color(Color32::from_rgb(200, 200, 200))
                 .strong(),
-            );
-        }*/
+            |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("Level:");
+                    egui::ComboBox::new("log_console_level", "")
+                        .selected_text(self.log_console_level.to_string())
+                        .show_ui(ui, |ui| {
+                            for level in [
+                                log::LevelFilter::Trace,
+                                log::LevelFilter::Debug,
+                                log::LevelFilter::Info,
+                                log::LevelFilter::Warn,
+                                log::LevelFilter::Error,
+                            ] {
+                                ui.selectable_value(
+                                    &mut self.log_console_level,
+                                    level,
+                                    level.to_string(),
+                                );
+                            }
+                        });
+                    ui.label("Filter:");
+                    ui.text_edit_singleline(&mut self.log_console_search);
+                });
+
+                let buffer = self.log_console.lock().unwrap();
+                egui::ScrollArea::vertical()
+                    .max_height(240.0)
+                    .auto_shrink([false, false])
+                    .stick_to_bottom(true)
+                    .show(ui, |ui| {
+                        for record in buffer.iter() {
+                            if record.level > self.log_console_level {
+                                continue;
+                            }
+                            if !self.log_console_search.is_empty()
+                                && !record
+                                    .message
+                                    .to_lowercase()
+                                    .contains(&self.log_console_search.to_lowercase())
+                            {
+                                continue;
+                            }
+                            let color = match record.level {
+                                log::Level::Error => Color32::RED,
+                                log::Level::Warn => Color32::YELLOW,
+                                log::Level::Info => Color32::LIGHT_GREEN,
+                                log::Level::Debug => Color32::LIGHT_BLUE,
+                                log::Level::Trace => Color32::GRAY,
+                            };
+                            ui.colored_label(
+                                color,
+                                format!(
+                                    "[{:>5.1}s] {:<5} {}: {}",
+                                    record.time.elapsed().as_secs_f32(),
+                                    record.level,
+                                    record.target,
+                                    record.message
+                                ),
+                            );
+                        }
+                    });
+            },
+        );
     }
 }
@@ -20,19 +20,34 @@ impl CBZViewerApp {
         if self.total_pages > 0 {
             let response = self.display_central_image_area(ctx, self.total_pages);
 
-            // Check if mouse is over the zoom area and there is a scroll
-            if let Some(cursor_pos) = ctx.input(|i| i.pointer.hover_pos()) {
-                let _zoomed = handle_zoom(
-                    &mut self.zoom,
-                    &mut self.pan_offset,
-                    cursor_pos,
-                    response.rect,
-                    ctx.input(|i| i.raw_scroll_delta.y),
-                    0.05,
-                    10.0,
-                    &mut self.texture_cache, // pass cursor_pos here
-                    &mut self.has_initialised_zoom,
-                );
+            // Check if mouse is over the zoom area and there is a scroll.
+            // In continuous scroll mode the scroll wheel advances reading
+            // position instead (handled by `draw_continuous_scroll`), so
+            // don't also feed it to the zoom handler.
+            if !self.continuous_scroll_mode {
+                if let Some(cursor_pos) = ctx.input(|i| i.pointer.hover_pos()) {
+                    let zoomed = handle_zoom(
+                        &mut self.zoom,
+                        &mut self.pan_offset,
+                        cursor_pos,
+                        response.rect,
+                        ctx.input(|i| i.raw_scroll_delta.y),
+                        0.05,
+                        10.0,
+                        &mut self.texture_cache, // pass cursor_pos here
+                        &mut self.has_initialised_zoom,
+                    );
+
+                    // Zooming out (especially near a corner) can push the
+                    // cursor-anchored pan outside the image bounds; clamping
+                    // only on `drag_stopped` left it stuck out of range until
+                    // the next drag, so clamp right away as well.
+                    if zoomed {
+                        if let Some(total_size) = self.current_page_dims() {
+                            clamp_pan(self, total_size, response.rect);
+                        }
+                    }
+                }
             }
 
             self.handle_input(ctx);
@@ -94,9 +109,41 @@ impl CBZViewerApp {
                     modules::ui_navigation(self, ui);
                 });
             });
+            if !self.tabs.is_empty() {
+                self.display_tab_strip(ui);
+            }
         });
     }
 
+    /// Draw the tab strip: one button per open comic plus a close ("x")
+    /// button, so several archives can stay open and be switched between.
+    pub fn display_tab_strip(&mut self, ui: &mut Ui) {
+        let tab_count = self.tabs.len();
+        let mut switch_to = None;
+        let mut close = None;
+        ui.horizontal(|ui| {
+            for idx in 0..tab_count {
+                ui.horizontal(|ui| {
+                    let label = self.tab_title(idx);
+                    let selected = idx == self.active_tab;
+                    if ui.selectable_label(selected, label).clicked() {
+                        switch_to = Some(idx);
+                    }
+                    if ui.small_button("\u{e5cd}").clicked() {
+                        close = Some(idx);
+                    }
+                });
+                ui.separator();
+            }
+        });
+        if let Some(idx) = switch_to {
+            self.switch_tab(idx);
+        }
+        if let Some(idx) = close {
+            self.close_tab(idx);
+        }
+    }
+
     pub fn display_notification_bar(&mut self, ctx: &Context) {
         /*if let Ok(logger) = self.ui_logger.lock() {
             if logger.message.is_some() {
@@ -160,6 +207,17 @@ impl CBZViewerApp {
             let response = ui.allocate_rect(image_area, egui::Sense::click_and_drag());
             response_opt = Some(response.clone());
 
+            // Upload any textures the decode worker pool finished since the
+            // last frame, then queue the next batch of neighbors to decode.
+            self.texture_cache.poll_ready(ctx);
+            self.texture_cache.prefetch(
+                self.current_page,
+                total_pages,
+                TEXTURE_PREFETCH_RADIUS,
+                self.zoom,
+                &self.image_lru,
+            );
+
             // Load images from image_lru with a short lock scope
             let (loaded1, loaded2, single_loaded) = {
                 let mut image_lru = self.image_lru.lock().unwrap();
@@ -184,9 +242,16 @@ impl CBZViewerApp {
 
             // Determine total size for clamping pan
             let total_size = if self.double_page_mode {
-                if let (Some(l1), Some(l2)) = (&loaded1, &loaded2) {
-                    let (w1, h1) = l1.image.dimensions();
-                    let (w2, h2) = l2.image.dimensions();
+                let paired = match (&loaded1, &loaded2) {
+                    (Some(l1), Some(l2)) => {
+                        !is_spread_page(l1, self.spread_aspect_threshold)
+                            && !is_spread_page(l2, self.spread_aspect_threshold)
+                    }
+                    _ => false,
+                };
+                if paired {
+                    let (w1, h1) = loaded1.as_ref().unwrap().image.dimensions();
+                    let (w2, h2) = loaded2.as_ref().unwrap().image.dimensions();
                     (w1 + w2, h1.max(h2))
                 } else if let Some(l1) = &loaded1 {
                     l1.image.dimensions()
@@ -203,20 +268,24 @@ impl CBZViewerApp {
 
             // Handle pan with a closure for clamping
             // Call handle_pan without closure
-            handle_pan(
-                &mut self.pan_offset,
-                &mut self.drag_start,
-                &mut self.original_pan_offset,
-                &response,
-            );
+            if !self.continuous_scroll_mode {
+                handle_pan(
+                    &mut self.pan_offset,
+                    &mut self.drag_start,
+                    &mut self.original_pan_offset,
+                    &response,
+                );
+            }
 
             // Clamp pan after dragging ends
-            if response.drag_stopped() {
+            if response.drag_stopped() && !self.continuous_scroll_mode {
                 clamp_pan(self, total_size, image_area);
             }
 
             // Drawing happens after image_lru lock is dropped and pan handled
-            if self.double_page_mode {
+            if self.continuous_scroll_mode {
+                self.draw_continuous_scroll(ui, image_area, total_pages, &response);
+            } else if self.double_page_mode {
                 if let (Some(l1), Some(l2)) = (&loaded1, &loaded2) {
                     if !self.has_initialised_zoom {
                         self.reset_zoom(image_area, l1);
@@ -227,10 +296,11 @@ impl CBZViewerApp {
                         Some(l2),
                         image_area,
                         self.zoom,
-                        PAGE_MARGIN_SIZE as f32,
+                        self.settings.page_margin_size as f32,
                         !self.right_to_left,
                         self.pan_offset,
                         &mut self.texture_cache,
+                        self.spread_aspect_threshold,
                     );
                 } else if let Some(l1) = &loaded1 {
                     if !self.has_initialised_zoom {
@@ -244,6 +314,9 @@ impl CBZViewerApp {
                         self.pan_offset,
                         &mut self.texture_cache,
                     );
+                } else if let Some(message) = self.page_errors.lock().unwrap().get(&self.current_page)
+                {
+                    draw_page_error(ui, image_area, message);
                 } else {
                     draw_spinner(ui, image_area);
                 }
@@ -260,12 +333,156 @@ impl CBZViewerApp {
                         self.pan_offset,
                         &mut self.texture_cache,
                     );
+                } else if let Some(message) = self.page_errors.lock().unwrap().get(&self.current_page)
+                {
+                    draw_page_error(ui, image_area, message);
                 } else {
                     draw_spinner(ui, image_area);
                 }
             }
+
+            // Overlay the current page's manifest comment, if any and not
+            // already dismissed by the reader.
+            if !self.continuous_scroll_mode {
+                let comment = self.archive.as_ref().and_then(|archive| {
+                    let archive = archive.lock().ok()?;
+                    archive
+                        .manifest
+                        .meta
+                        .comments
+                        .as_ref()?
+                        .get(self.current_page)
+                        .filter(|c| !c.is_empty())
+                        .cloned()
+                });
+                if let Some(text) = comment {
+                    if self.dismissed_comment_page != Some(self.current_page)
+                        && draw_page_comment(ui, image_area, &text)
+                    {
+                        self.dismissed_comment_page = Some(self.current_page);
+                    }
+                }
+            }
         });
 
         response_opt.expect("Central panel UI always provides a response")
     }
+
+    /// Draw the continuous vertical "webtoon" scroll mode: pages are stacked
+    /// top-to-bottom on a virtual canvas and the viewport slides over it via
+    /// `scroll_offset`, instead of flipping discretely between pages. Only
+    /// the pages intersecting the visible band (plus a small look-ahead
+    /// window) are decoded/uploaded; `scroll_offset` is clamped against the
+    /// summed height of all pages rather than `clamp_pan`'s per-page bounds.
+    fn draw_continuous_scroll(
+        &mut self,
+        ui: &mut Ui,
+        image_area: Rect,
+        total_pages: usize,
+        response: &egui::Response,
+    ) {
+        if total_pages == 0 {
+            draw_spinner(ui, image_area);
+            return;
+        }
+
+        const ESTIMATED_PAGE_ASPECT: f32 = 1.45;
+        if self.page_heights.len() != total_pages {
+            self.page_heights
+                .resize(total_pages, image_area.width() * ESTIMATED_PAGE_ASPECT);
+        }
+
+        // Reflow: replace estimates with real heights for pages that are
+        // decoded. Doing so shifts every page below the one that just
+        // reflowed, so anchor on the page currently centered in the
+        // viewport: measure the cumulative height above it before and
+        // after the reflow and carry the difference into `scroll_offset`,
+        // otherwise the viewport would visibly jump under the reader.
+        let margin = self.settings.page_margin_size as f32;
+        let anchor = self.current_page.min(total_pages - 1);
+        let height_above_anchor =
+            |heights: &[f32], zoom: f32| -> f32 { heights[..anchor].iter().map(|h| h * zoom + margin).sum() };
+        let old_top = height_above_anchor(&self.page_heights, self.zoom);
+
+        for i in 0..total_pages {
+            if let Some(loaded) = self.image_lru.lock().unwrap().get(&i) {
+                let (w, h) = loaded.image.dimensions();
+                if w > 0 {
+                    self.page_heights[i] = image_area.width() * (h as f32 / w as f32);
+                }
+            }
+        }
+
+        let new_top = height_above_anchor(&self.page_heights, self.zoom);
+        self.scroll_offset += new_top - old_top;
+
+        // Prefix sum of scaled, zoomed page heights (plus inter-page margin).
+        let mut prefix = Vec::with_capacity(total_pages + 1);
+        prefix.push(0.0f32);
+        for h in &self.page_heights {
+            let top = *prefix.last().unwrap();
+            prefix.push(top + h * self.zoom + margin);
+        }
+        let total_height = *prefix.last().unwrap_or(&0.0);
+
+        let max_scroll = (total_height - image_area.height()).max(0.0);
+        self.scroll_offset = self.scroll_offset.clamp(0.0, max_scroll);
+        let viewport_top = self.scroll_offset;
+        let viewport_bottom = viewport_top + image_area.height();
+
+        let visible_first = prefix
+            .partition_point(|&y| y <= viewport_top)
+            .saturating_sub(1)
+            .min(total_pages - 1);
+        let mut visible_last = visible_first;
+        while visible_last + 1 < total_pages && prefix[visible_last + 1] <= viewport_bottom {
+            visible_last += 1;
+        }
+
+        // Draw one extra page of overscan past each edge of the visible
+        // range, so a page scrolled just out of view is already on screen
+        // (and its texture already uploaded) the moment it's needed again.
+        const OVERSCAN_PAGES: usize = 1;
+        let first = visible_first.saturating_sub(OVERSCAN_PAGES);
+        let last = (visible_last + OVERSCAN_PAGES).min(total_pages - 1);
+        self.texture_cache.evict_animated_outside(first, last);
+        self.texture_cache.evict_outside(first, last);
+
+        let viewport_center = (viewport_top + viewport_bottom) / 2.0;
+        let mut center_page = visible_first;
+
+        for i in first..=last {
+            let page_top = prefix[i] - viewport_top;
+            let page_height = self.page_heights[i] * self.zoom;
+            let page_rect = Rect::from_min_size(
+                egui::pos2(image_area.left(), image_area.top() + page_top),
+                Vec2::new(image_area.width(), page_height),
+            );
+
+            if prefix[i] <= viewport_center && viewport_center <= prefix[i] + page_height {
+                center_page = i;
+            }
+
+            let loaded = self.image_lru.lock().unwrap().get(&i).cloned();
+            if let Some(loaded) = loaded {
+                draw_single_page(ui, &loaded, page_rect, self.zoom, Vec2::ZERO, &mut self.texture_cache);
+            } else if let Some(message) = self.page_errors.lock().unwrap().get(&i) {
+                draw_page_error(ui, page_rect, message);
+            } else {
+                draw_spinner(ui, page_rect);
+            }
+        }
+
+        self.current_page = center_page;
+
+        // Scroll wheel and click-drag both move the virtual canvas instead
+        // of the page zoom/pan they drive in discrete-page mode.
+        let scroll_delta = ui.input(|i| i.raw_scroll_delta.y);
+        if scroll_delta != 0.0 {
+            self.scroll_offset = (self.scroll_offset - scroll_delta).clamp(0.0, max_scroll);
+        }
+        if response.dragged() {
+            self.scroll_offset = (self.scroll_offset - response.drag_delta().y).clamp(0.0, max_scroll);
+        }
+    }
 }
@@ -0,0 +1,156 @@
+//! In-app directory browser for opening archives without a native file
+//! dialog, plus a small newline-delimited recent-directories history
+//! (separate from `RecentsStore`, which tracks opened *archives* rather
+//! than folders browsed) persisted under the platform cache dir.
+
+use crate::prelude::*;
+use std::path::Path;
+
+const MAX_RECENT_DIRS: usize = 10;
+
+fn recent_dirs_path() -> PathBuf {
+    dirs::cache_dir()
+        .unwrap_or_else(std::env::temp_dir)
+        .join("comic_suite")
+        .join("recent_dirs.txt")
+}
+
+/// Read the recent-directories history, dropping entries that no longer
+/// exist (moved/deleted since last run) rather than surfacing a dead link.
+pub fn load_recent_dirs() -> Vec<PathBuf> {
+    std::fs::read_to_string(recent_dirs_path())
+        .map(|s| {
+            s.lines()
+                .map(PathBuf::from)
+                .filter(|p| p.is_dir())
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Move/insert `dir` at the front of the recent-directories history,
+/// truncate to `MAX_RECENT_DIRS`, and persist.
+pub fn record_recent_dir(dir: &Path) {
+    let mut dirs = load_recent_dirs();
+    dirs.retain(|d| d != dir);
+    dirs.insert(0, dir.to_path_buf());
+    dirs.truncate(MAX_RECENT_DIRS);
+
+    let path = recent_dirs_path();
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    let contents = dirs
+        .iter()
+        .map(|d| d.to_string_lossy().into_owned())
+        .collect::<Vec<_>>()
+        .join("\n");
+    let _ = std::fs::write(path, contents);
+}
+
+/// One entry in a browsed directory's listing.
+struct DirEntry {
+    path: PathBuf,
+    name: String,
+    is_dir: bool,
+}
+
+fn list_dir(dir: &Path) -> Vec<DirEntry> {
+    let exts = crate::comic_exts!();
+    let Ok(read_dir) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut entries: Vec<DirEntry> = read_dir
+        .filter_map(|e| e.ok())
+        .filter_map(|e| {
+            let path = e.path();
+            let name = path.file_name()?.to_string_lossy().into_owned();
+            let is_dir = path.is_dir();
+            if !is_dir {
+                let ext = path.extension()?.to_string_lossy().to_lowercase();
+                if !exts.contains(&ext.as_str()) {
+                    return None;
+                }
+            }
+            Some(DirEntry { path, name, is_dir })
+        })
+        .collect();
+
+    entries.sort_by(|a, b| match (a.is_dir, b.is_dir) {
+        (true, false) => std::cmp::Ordering::Less,
+        (false, true) => std::cmp::Ordering::Greater,
+        _ => a.name.to_lowercase().cmp(&b.name.to_lowercase()),
+    });
+    entries
+}
+
+impl CBZViewerApp {
+    pub fn display_file_browser(&mut self, ctx: &egui::Context) {
+        if !self.show_file_browser {
+            return;
+        }
+        let mut show = self.show_file_browser;
+        let mut navigate_to = None;
+        let mut open_archive = None;
+
+        egui::Window::new("File Browser")
+            .open(&mut show)
+            .resizable(true)
+            .vscroll(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if ui.button("\u{f062}").on_hover_text("Up one level").clicked() {
+                        if let Some(parent) = self.file_browser_dir.parent() {
+                            navigate_to = Some(parent.to_path_buf());
+                        }
+                    }
+                    ui.label(self.file_browser_dir.to_string_lossy());
+                });
+                ui.separator();
+
+                let recent_dirs = load_recent_dirs();
+                if !recent_dirs.is_empty() {
+                    ui.menu_button("Recent Folders", |ui| {
+                        for dir in recent_dirs {
+                            if ui.button(dir.to_string_lossy()).clicked() {
+                                navigate_to = Some(dir);
+                                ui.close_menu();
+                            }
+                        }
+                    });
+                    ui.separator();
+                }
+
+                egui::ScrollArea::vertical()
+                    .max_height(360.0)
+                    .show(ui, |ui| {
+                        for entry in list_dir(&self.file_browser_dir) {
+                            let label = if entry.is_dir {
+                                format!("\u{f07b} {}", entry.name)
+                            } else {
+                                format!("\u{f15b} {}", entry.name)
+                            };
+                            if ui.selectable_label(false, label).clicked() {
+                                if entry.is_dir {
+                                    navigate_to = Some(entry.path);
+                                } else {
+                                    open_archive = Some(entry.path);
+                                }
+                            }
+                        }
+                    });
+            });
+
+        self.show_file_browser = show;
+
+        if let Some(dir) = navigate_to {
+            self.file_browser_dir = dir.clone();
+            record_recent_dir(&dir);
+        }
+        if let Some(path) = open_archive {
+            self.new_page = Some(path);
+            self.show_file_browser = false;
+        }
+    }
+}
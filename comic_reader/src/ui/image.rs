@@ -11,6 +11,46 @@ pub fn draw_spinner(ui: &mut Ui, area: Rect) {
     });
 }
 
+/// Draw a page's load error centered in the given area, in place of
+/// `draw_spinner`'s pending-state spinner, for a page whose most recent load
+/// attempt failed (e.g. a web-archive page that couldn't be fetched).
+pub fn draw_page_error(ui: &mut Ui, area: Rect, message: &str) {
+    ui.allocate_ui_at_rect(area, |ui| {
+        ui.vertical_centered(|ui| {
+            ui.add_space(area.height() / 2.0 - 20.0);
+            ui.colored_label(Color32::LIGHT_RED, "\u{f071}");
+            ui.colored_label(Color32::LIGHT_RED, message);
+        });
+    });
+}
+
+/// Draw a page's `Metadata.comments` entry as a dismissible caption overlay
+/// anchored to the bottom of `area`, for translated/annotated scanlations.
+/// Returns `true` if the close button was clicked, so the caller can record
+/// that this page's comment has been dismissed.
+pub fn draw_page_comment(ui: &mut Ui, area: Rect, text: &str) -> bool {
+    let mut dismissed = false;
+    let caption_rect = Rect::from_min_max(
+        egui::pos2(area.left(), area.bottom() - 64.0),
+        egui::pos2(area.right(), area.bottom()),
+    );
+    ui.allocate_ui_at_rect(caption_rect, |ui| {
+        egui::Frame::none()
+            .fill(Color32::from_black_alpha(180))
+            .rounding(4.0)
+            .inner_margin(egui::Margin::symmetric(10.0, 6.0))
+            .show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(text).color(Color32::WHITE));
+                    if ui.small_button("\u{e5cd}").clicked() {
+                        dismissed = true;
+                    }
+                });
+            });
+    });
+    dismissed
+}
+
 /// Macro to handle drawing a static image using the cache.
 /// Reduces boilerplate for both single and dual page drawing.
 macro_rules! draw_static {
@@ -20,31 +60,94 @@ macro_rules! draw_static {
             _ => return,
         };
         let $disp_size = Vec2::new(w as f32 * $zoom, h as f32 * $zoom);
+        let rect = Rect::from_center_size($area.center() + $pan, $disp_size);
 
-        let ctx = $ui.ctx().clone();
-        let $handle = if let Some(handle) = $cache.get_single($loaded.index, $zoom) {
+        if let PageImage::Static(img) = &$loaded.image {
+            draw_static_page_at_rect($ui, img, $loaded.index, $zoom, rect, $cache);
+        }
+    }};
+}
+
+/// Draw a static page into `rect`: one cached texture for pages that fit
+/// within the backend's `max_texture_side`, or a grid of same-sized tiles
+/// — each its own cached `TextureHandle`, placed into adjacent sub-rects so
+/// the seams line up exactly — for scans too large to upload in one piece
+/// (common for tall webtoon strips and oversized two-page scans).
+fn draw_static_page_at_rect(
+    ui: &mut Ui,
+    img: &DynamicImage,
+    page_idx: usize,
+    zoom: f32,
+    rect: Rect,
+    cache: &mut TextureCache,
+) {
+    use crate::cache::texture_cache::{color_image_for_zoom, display_image_for_zoom, TextureKey};
+
+    let max_side = ui.ctx().input(|i| i.max_texture_side) as u32;
+    let display_img = display_image_for_zoom(img, zoom);
+    let (dw, dh) = display_img.dimensions();
+    let ctx = ui.ctx().clone();
+
+    if dw <= max_side && dh <= max_side {
+        let handle = if let Some(handle) = cache.get_single(page_idx, zoom) {
             handle.clone()
         } else {
-            let color_img = match &$loaded.image {
-                PageImage::Static(img) => {
-                    egui::ColorImage::from_rgba_unmultiplied([w as usize, h as usize], &img.to_rgba8())
-                }
-                _ => return,
-            };
+            let color_img = color_image_for_zoom(img, zoom);
             let handle = ctx.load_texture(
-                format!("tex{}_{}", $loaded.index, $zoom),
+                format!("tex{}_{}", page_idx, zoom),
                 color_img,
                 egui::TextureOptions::default(),
             );
-            $cache.set_single($loaded.index, $zoom, handle.clone());
+            cache.set_single(page_idx, zoom, handle.clone());
             handle
         };
-
-        let rect = Rect::from_center_size($area.center() + $pan, $disp_size);
-        $ui.allocate_ui_at_rect(rect, |ui| {
-            ui.add(Image::from_texture(&$handle).fit_to_exact_size($disp_size));
+        ui.allocate_ui_at_rect(rect, |ui| {
+            ui.add(Image::from_texture(&handle).fit_to_exact_size(rect.size()));
         });
-    }};
+        return;
+    }
+
+    let key = TextureKey::new(page_idx, zoom);
+    let cols = dw.div_ceil(max_side);
+    let rows = dh.div_ceil(max_side);
+    for tile_y in 0..rows {
+        for tile_x in 0..cols {
+            let x0 = tile_x * max_side;
+            let y0 = tile_y * max_side;
+            let tw = max_side.min(dw - x0);
+            let th = max_side.min(dh - y0);
+            let handle = if let Some(handle) = cache.get_tile(key, tile_x, tile_y) {
+                handle.clone()
+            } else {
+                let tile = display_img.crop_imm(x0, y0, tw, th);
+                let color_img = egui::ColorImage::from_rgba_unmultiplied(
+                    [tw as usize, th as usize],
+                    &tile.to_rgba8(),
+                );
+                let handle = ctx.load_texture(
+                    format!("tex{}_{}_tile{}_{}", page_idx, zoom, tile_x, tile_y),
+                    color_img,
+                    egui::TextureOptions::default(),
+                );
+                cache.set_tile(key, tile_x, tile_y, handle.clone());
+                handle
+            };
+            let tile_rect = Rect::from_min_size(
+                rect.min
+                    + Vec2::new(
+                        x0 as f32 / dw as f32 * rect.width(),
+                        y0 as f32 / dh as f32 * rect.height(),
+                    ),
+                Vec2::new(
+                    tw as f32 / dw as f32 * rect.width(),
+                    th as f32 / dh as f32 * rect.height(),
+                ),
+            );
+            ui.allocate_ui_at_rect(tile_rect, |ui| {
+                ui.add(Image::from_texture(&handle).fit_to_exact_size(tile_rect.size()));
+            });
+        }
+    }
 }
 
 /// Draw a single page image, using the texture cache for efficiency.
@@ -57,16 +160,18 @@ pub fn draw_single_page(
     cache: &mut TextureCache,
 ) {
     match &loaded.image {
-        PageImage::AnimatedGif { .. } => draw_gif(ui, loaded, area, zoom, pan, cache),
+        PageImage::Animated { .. } => draw_gif(ui, loaded, area, zoom, pan, cache),
         PageImage::Static(_) => draw_static!(ui, loaded, area, zoom, pan, cache, disp_size, handle),
+        #[cfg(feature = "svg")]
+        PageImage::Vector(tree) => draw_vector_page(ui, loaded, tree, area, zoom, pan, cache),
     }
 }
 
-/// Macro to handle dual page drawing logic, including GIF/static dispatch.
+/// Macro to handle dual page drawing logic, including animated/static dispatch.
 macro_rules! draw_page_at_rect {
     ($ui:expr, $loaded:expr, $rect:expr, $disp_size:expr, $handle:expr, $cache:expr, $zoom:expr, $pan:expr) => {
         match &$loaded.image {
-            PageImage::AnimatedGif { .. } => {
+            PageImage::Animated { .. } => {
                 draw_gif_at_rect($ui, $loaded, $rect, $zoom, $pan, $cache);
             }
             PageImage::Static(_) => {
@@ -76,11 +181,34 @@ macro_rules! draw_page_at_rect {
                     });
                 }
             }
+            #[cfg(feature = "svg")]
+            PageImage::Vector(_) => {
+                if let Some(handle) = &$handle {
+                    $ui.allocate_ui_at_rect($rect, |ui| {
+                        ui.add(Image::from_texture(handle).fit_to_exact_size($disp_size));
+                    });
+                }
+            }
         }
     };
 }
 
+/// A page is a wide "spread" scan (e.g. a double-page splash digitized as
+/// one image) when its width/height ratio exceeds `threshold`. Such pages
+/// render solo in dual-page mode rather than being squeezed beside a
+/// second page.
+pub fn is_spread_page(loaded: &LoadedPage, threshold: f32) -> bool {
+    let (w, h) = loaded.image.dimensions();
+    h > 0 && (w as f32 / h as f32) > threshold
+}
+
 /// Draw two pages side by side, using the texture cache for efficiency.
+///
+/// If `loaded_left` is itself a spread (see `is_spread_page`), or `loaded_right`
+/// would be, the pairing is broken and only `loaded_left` is drawn, centered
+/// alone. Returns how many of the two candidate pages were actually consumed
+/// (1 or 2), so callers stepping through pages by a fixed pair size can stay
+/// in sync with where pairs actually fall.
 pub fn draw_dual_page(
     ui: &mut Ui,
     loaded_left: &LoadedPage,
@@ -91,7 +219,15 @@ pub fn draw_dual_page(
     left_first: bool,
     pan: Vec2,
     cache: &mut TextureCache,
-) {
+    spread_threshold: f32,
+) -> usize {
+    let loaded_right = if is_spread_page(loaded_left, spread_threshold) {
+        None
+    } else {
+        loaded_right.filter(|r| !is_spread_page(r, spread_threshold))
+    };
+    let consumed = if loaded_right.is_some() { 2 } else { 1 };
+
     let ctx = ui.ctx().clone();
 
     // Helper: get display size and texture handle for a page
@@ -108,11 +244,7 @@ pub fn draw_dual_page(
                 let handle = if let Some(h) = cache.get_single(page.index, zoom) {
                     Some(h.clone())
                 } else {
-                    let rgba_bytes = img.to_rgba8();
-                    let color_img = egui::ColorImage::from_rgba_unmultiplied(
-                        [w as usize, h as usize],
-                        rgba_bytes.as_flat_samples().as_slice(),
-                    );
+                    let color_img = crate::cache::texture_cache::color_image_for_zoom(img, zoom);
                     let h = ctx.load_texture(
                         format!("tex{}_{}", page.index, zoom),
                         color_img,
@@ -123,17 +255,41 @@ pub fn draw_dual_page(
                 };
                 Some((disp_size, handle))
             }
-            PageImage::AnimatedGif { frames, .. } if !frames.is_empty() => {
-                let (w, h) = (frames[0].size[0] as u32, frames[0].size[1] as u32);
+            PageImage::Animated { frame_table, .. } if !frame_table.is_empty() => {
+                let (w, h) = (frame_table[0].width, frame_table[0].height);
                 Some((Vec2::new(w as f32 * zoom, h as f32 * zoom), None))
             }
+            #[cfg(feature = "svg")]
+            PageImage::Vector(tree) => {
+                let (w, h) = page.image.dimensions();
+                let disp_size = Vec2::new(w as f32 * zoom, h as f32 * zoom);
+                let handle = if let Some(h) = cache.get_single(page.index, zoom) {
+                    Some(h.clone())
+                } else {
+                    comic_archive::decode::rasterize_svg_tree(tree, zoom).map(|rgba| {
+                        let (rw, rh) = rgba.dimensions();
+                        let color_img = egui::ColorImage::from_rgba_unmultiplied(
+                            [rw as usize, rh as usize],
+                            rgba.as_raw(),
+                        );
+                        let h = ctx.load_texture(
+                            format!("tex{}_{}", page.index, zoom),
+                            color_img,
+                            egui::TextureOptions::default(),
+                        );
+                        cache.set_single(page.index, zoom, h.clone());
+                        h
+                    })
+                };
+                Some((disp_size, handle))
+            }
             _ => None,
         }
     }
 
     let (disp_size1, handle1) = match get_page_data(loaded_left, zoom, cache, &ctx) {
         Some(data) => data,
-        None => return,
+        None => return consumed,
     };
 
     let (disp_size2, handle2) = if let Some(loaded2) = loaded_right {
@@ -179,9 +335,55 @@ pub fn draw_dual_page(
         let rect = egui::Rect::from_center_size(center, disp_size1);
         draw_page_at_rect!(ui, loaded_left, rect, disp_size1, handle1, cache, zoom, pan);
     }
+
+    consumed
+}
+
+/// Draw an SVG page, rasterizing it at the current `zoom` and caching the
+/// result under the same `(page_idx, zoom)` key the static/dual paths use,
+/// so re-zooming produces a crisp new texture instead of scaling a blurry
+/// fixed-resolution bitmap.
+#[cfg(feature = "svg")]
+pub fn draw_vector_page(
+    ui: &mut Ui,
+    loaded: &LoadedPage,
+    tree: &usvg::Tree,
+    area: Rect,
+    zoom: f32,
+    pan: Vec2,
+    cache: &mut TextureCache,
+) {
+    let (base_w, base_h) = loaded.image.dimensions();
+    let disp_size = Vec2::new(base_w as f32 * zoom, base_h as f32 * zoom);
+
+    let ctx = ui.ctx().clone();
+    let handle = if let Some(handle) = cache.get_single(loaded.index, zoom) {
+        handle.clone()
+    } else {
+        let Some(rgba) = comic_archive::decode::rasterize_svg_tree(tree, zoom) else {
+            warn!("Failed to rasterize SVG page: {}", loaded.filename);
+            return;
+        };
+        let (w, h) = rgba.dimensions();
+        let color_img =
+            egui::ColorImage::from_rgba_unmultiplied([w as usize, h as usize], rgba.as_raw());
+        let handle = ctx.load_texture(
+            format!("svg{}_{}", loaded.index, zoom),
+            color_img,
+            egui::TextureOptions::default(),
+        );
+        cache.set_single(loaded.index, zoom, handle.clone());
+        handle
+    };
+
+    let rect = Rect::from_center_size(area.center() + pan, disp_size);
+    ui.allocate_ui_at_rect(rect, |ui| {
+        ui.add(Image::from_texture(&handle).fit_to_exact_size(disp_size));
+    });
 }
 
-/// Draw a GIF in the given area by forwarding to `draw_gif_at_rect`.
+/// Draw an animated page (GIF, animated WebP, or APNG) in the given area by
+/// forwarding to `draw_gif_at_rect`.
 pub fn draw_gif(
     ui: &mut Ui,
     loaded: &LoadedPage,
@@ -190,14 +392,14 @@ pub fn draw_gif(
     pan: Vec2,
     cache: &mut TextureCache,
 ) {
-    let (w, h) = if let PageImage::AnimatedGif { frames, .. } = &loaded.image {
-        if frames.is_empty() {
-            warn!("GIF has no frames: {}", loaded.filename);
+    let (w, h) = if let PageImage::Animated { frame_table, .. } = &loaded.image {
+        let Some(first) = frame_table.first() else {
+            warn!("Animated page has no frames: {}", loaded.filename);
             return;
-        }
-        (frames[0].size[0] as f32, frames[0].size[1] as f32)
+        };
+        (first.width as f32, first.height as f32)
     } else {
-        warn!("draw_gif called on non-gif image");
+        warn!("draw_gif called on non-animated image");
         return;
     };
 
@@ -207,8 +409,15 @@ pub fn draw_gif(
     draw_gif_at_rect(ui, loaded, rect, zoom, pan, cache);
 }
 
-/// Draw a GIF at the specified rect, using the texture cache to avoid reloads.
-/// Handles frame timing and texture management for animated playback.
+/// Draw an animated page at the specified rect, using the texture cache to
+/// avoid reloads. Handles frame timing and texture management for playback;
+/// format-agnostic since `PageImage::Animated` already holds a frame table
+/// pointing into the page's on-disk scratch file.
+///
+/// Only the frames within `ANIMATION_FRAME_RING_SIZE` of the one currently
+/// playing stay uploaded as textures; everything else is read back from the
+/// scratch file via `read_scratch_frame` as playback reaches it, so a long
+/// animation's GPU footprint stays flat regardless of its total frame count.
 pub fn draw_gif_at_rect(
     ui: &mut Ui,
     loaded: &LoadedPage,
@@ -217,21 +426,32 @@ pub fn draw_gif_at_rect(
     _pan: Vec2,
     cache: &mut TextureCache,
 ) {
-    if let PageImage::AnimatedGif {
-        frames,
+    if let PageImage::Animated {
+        scratch_path,
+        frame_table,
         delays,
         start_time,
+        loop_count,
     } = &loaded.image
     {
-        if frames.is_empty() {
-            warn!("GIF has no frames: {}", loaded.filename);
+        if frame_table.is_empty() {
+            warn!("Animated page has no frames: {}", loaded.filename);
             return;
         }
 
         // Compute which frame to show based on elapsed time and per-frame delays
         let elapsed = start_time.elapsed().as_millis() as u64;
         let total_duration: u64 = delays.iter().map(|d| *d as u64).sum();
-        let t = elapsed % total_duration;
+
+        // Once a finite animation has played its full loop count, hold on
+        // the last frame instead of wrapping back to the first.
+        let cycles_done = elapsed / total_duration.max(1);
+        let finished = loop_count.is_some_and(|n| cycles_done >= n as u64);
+        let t = if finished {
+            total_duration.saturating_sub(1)
+        } else {
+            elapsed % total_duration
+        };
 
         let mut acc = 0u64;
         let mut idx = 0;
@@ -245,26 +465,43 @@ pub fn draw_gif_at_rect(
         }
 
         let ctx = ui.ctx().clone();
-        let key = format!("gif{}_{}", loaded.index, idx);
 
-        let handle = if let Some(handle) = cache.get_animated(&key) {
+        let handle = if let Some(handle) = cache.get_animated(loaded.index, idx) {
             handle.clone()
         } else {
+            let Some(color_image) =
+                crate::cache::image_cache::read_scratch_frame(scratch_path, &frame_table[idx])
+            else {
+                warn!(
+                    "Failed to read animation frame {} back from scratch file: {}",
+                    idx, loaded.filename
+                );
+                return;
+            };
             let new_handle = ctx.load_texture(
-                key.clone(),
-                frames[idx].clone(),
+                format!("gif{}_{}", loaded.index, idx),
+                color_image,
                 egui::TextureOptions::default(),
             );
-            cache.set_animated(key, new_handle.clone());
+            cache.set_animated(loaded.index, idx, new_handle.clone());
             new_handle
         };
+        cache.evict_animated_frames_outside_ring(loaded.index, idx, ANIMATION_FRAME_RING_SIZE);
 
         ui.allocate_ui_at_rect(rect, |ui| {
             ui.add(Image::from_texture(&handle).fit_to_exact_size(rect.size()));
         });
 
-        // Request repaint for smooth animation
-        ui.ctx().request_repaint();
+        // Only wake up again when this frame's delay actually expires,
+        // instead of repainting every frame and spinning the CPU while the
+        // GIF sits on an unchanged frame. A finite animation that has
+        // already played out its `loop_count` stays parked on its last
+        // frame and stops scheduling repaints altogether.
+        if !finished {
+            let remaining_ms = (acc + delays[idx] as u64).saturating_sub(t).max(1);
+            ui.ctx()
+                .request_repaint_after(std::time::Duration::from_millis(remaining_ms));
+        }
     }
 }
 
@@ -358,7 +595,7 @@ pub fn handle_zoom(
 
         *pan_offset = (*pan_offset - cursor_rel) * effective_factor + cursor_rel;
         *has_initialised_zoom = true;
-        texture_cache.clear();
+        texture_cache.invalidate_zoom(*zoom);
         return true;
     }
 
@@ -111,7 +111,7 @@ pub fn draw_central_image_area(
                     Some(l2),
                     image_area,
                     app.zoom,
-                    PAGE_MARGIN_SIZE as f32,
+                    app.settings.page_margin_size as f32,
                     !app.right_to_left,
                     app.pan_offset,
                     &mut app.texture_cache,
@@ -0,0 +1,182 @@
+//! In-app library browser: a thumbnail grid over a chosen folder of comics,
+//! with an incremental filename filter. Covers are decoded on background
+//! tokio tasks and land in `comic_archive::thumbnail_cache` (the same cache
+//! `display_recents_panel` draws from), so revisiting a folder re-lists
+//! instantly from cache instead of re-decoding every cover.
+
+use crate::prelude::*;
+use std::sync::mpsc::{Receiver, channel};
+
+/// Cover size for library tiles. Bigger than `RECENT_THUMB_SIZE` since the
+/// library grid is the primary way to browse a folder, not a small aside
+/// panel.
+const LIBRARY_THUMB_SIZE: u32 = 140;
+
+struct LibraryEntry {
+    path: PathBuf,
+    name: String,
+    texture: Option<TextureHandle>,
+}
+
+/// State for the in-app library browser window.
+#[derive(Default)]
+pub struct LibraryState {
+    pub open: bool,
+    pub folder: Option<PathBuf>,
+    pub filter: String,
+    entries: Vec<LibraryEntry>,
+    thumb_rx: Option<Receiver<(usize, Vec<u8>)>>,
+}
+
+impl LibraryState {
+    /// List comics directly under `folder` and kick off background cover
+    /// decoding for each. Not recursive, unlike `FolderImageArchive`'s page
+    /// walk, since each entry here is itself a whole comic rather than a
+    /// page within one.
+    pub fn open_folder(&mut self, folder: PathBuf) {
+        let exts = crate::comic_exts!();
+        let mut entries = Vec::new();
+        if let Ok(dir) = std::fs::read_dir(&folder) {
+            for entry in dir.flatten() {
+                let path = entry.path();
+                if !path.is_file() {
+                    continue;
+                }
+                let Some(ext) = path.extension().and_then(|e| e.to_str()) else {
+                    continue;
+                };
+                if !exts.contains(&ext.to_lowercase().as_str()) {
+                    continue;
+                }
+                let name = path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_default();
+                entries.push(LibraryEntry { path, name, texture: None });
+            }
+        }
+        entries.sort_by(|a, b| comic_archive::sort::natural_cmp(&a.name, &b.name));
+
+        let (tx, rx) = channel();
+        for (i, entry) in entries.iter().enumerate() {
+            let path = entry.path.clone();
+            let tx = tx.clone();
+            tokio::spawn(async move {
+                let Ok(mut archive) = ImageArchive::process(&path).await else {
+                    return;
+                };
+                let Some(first) = archive.list_images().into_iter().next() else {
+                    return;
+                };
+                if let Ok(bytes) = archive
+                    .generate_thumbnail(&first, LIBRARY_THUMB_SIZE, RECENT_THUMB_QUALITY)
+                    .await
+                {
+                    let _ = tx.send((i, bytes));
+                }
+            });
+        }
+
+        self.folder = Some(folder);
+        self.entries = entries;
+        self.thumb_rx = Some(rx);
+        self.open = true;
+    }
+
+    /// Upload any covers that finished decoding since the last frame.
+    fn drain_thumbnails(&mut self, ctx: &egui::Context) {
+        let Some(rx) = &self.thumb_rx else {
+            return;
+        };
+        while let Ok((index, bytes)) = rx.try_recv() {
+            let Some(entry) = self.entries.get_mut(index) else {
+                continue;
+            };
+            let Ok(image) = image::load_from_memory(&bytes) else {
+                continue;
+            };
+            let color = egui::ColorImage::from_rgba_unmultiplied(
+                [image.width() as usize, image.height() as usize],
+                &image.to_rgba8(),
+            );
+            entry.texture = Some(ctx.load_texture(
+                format!("library_thumb_{index}"),
+                color,
+                egui::TextureOptions::default(),
+            ));
+        }
+    }
+}
+
+impl CBZViewerApp {
+    /// Draw the library browser window, if open. Selecting a tile opens
+    /// that comic the same way the recent-files panel and file browser do,
+    /// through `new_page`.
+    pub fn display_library_browser(&mut self, ctx: &egui::Context) {
+        if !self.library.open {
+            return;
+        }
+        self.library.drain_thumbnails(ctx);
+
+        let mut open = self.library.open;
+        let mut selected = None;
+        egui::Window::new("Library")
+            .open(&mut open)
+            .resizable(true)
+            .vscroll(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    if let Some(folder) = &self.library.folder {
+                        ui.label(folder.to_string_lossy());
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Filter:");
+                    ui.text_edit_singleline(&mut self.library.filter);
+                });
+                ui.separator();
+
+                let filter = self.library.filter.to_lowercase();
+                egui::ScrollArea::vertical()
+                    .max_height(480.0)
+                    .show(ui, |ui| {
+                        ui.horizontal_wrapped(|ui| {
+                            for (i, entry) in self.library.entries.iter().enumerate() {
+                                if !filter.is_empty() && !entry.name.to_lowercase().contains(&filter)
+                                {
+                                    continue;
+                                }
+                                ui.vertical(|ui| {
+                                    let clicked = if let Some(texture) = &entry.texture {
+                                        ui.add(egui::ImageButton::new((
+                                            texture.id(),
+                                            Vec2::splat(LIBRARY_THUMB_SIZE as f32),
+                                        )))
+                                        .clicked()
+                                    } else {
+                                        ui.add_sized(
+                                            [LIBRARY_THUMB_SIZE as f32; 2],
+                                            egui::Spinner::new(),
+                                        )
+                                        .clicked()
+                                    };
+                                    ui.label(RichText::new(&entry.name).small());
+                                    if clicked {
+                                        selected = Some(i);
+                                    }
+                                });
+                            }
+                        });
+                    });
+            });
+
+        self.library.open = open;
+
+        if let Some(index) = selected {
+            if let Some(entry) = self.library.entries.get(index) {
+                self.new_page = Some(entry.path.clone());
+                self.library.open = false;
+            }
+        }
+    }
+}
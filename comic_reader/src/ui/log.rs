@@ -1,6 +1,7 @@
 //! UI logger for warnings and errors.
 
 use crate::prelude::*;
+use std::collections::VecDeque;
 
 #[derive(Clone, Copy, PartialEq, Eq)]
 pub enum UiLogLevel {
@@ -27,12 +28,24 @@ impl UiLogLevel {
     }
 }
 
-/// Logger that pushes warnings and errors to both log and UI.
+/// One recorded message in `UiLogger::history`.
+#[derive(Clone)]
+pub struct LogEntry {
+    pub time: Instant,
+    pub message: String,
+    pub level: UiLogLevel,
+}
+
+/// Logger that pushes warnings and errors to both the `log` crate and the
+/// UI: a transient toast (the most recent message, cleared by
+/// `clear_expired` after its timeout) plus a bounded history so earlier
+/// messages survive past their toast's timeout for review in the log panel.
 #[derive(Clone)]
 pub struct UiLogger {
     pub message: Option<(String, UiLogLevel)>,
     message_time: Option<Instant>,
     pub timeout_override: Option<u64>,
+    pub history: VecDeque<LogEntry>,
 }
 
 impl UiLogger {
@@ -42,11 +55,20 @@ impl UiLogger {
             message: None,
             message_time: None,
             timeout_override: None,
+            history: VecDeque::new(),
         }
     }
 
-    /// Internal helper to set message and timestamp.
+    /// Internal helper to set the toast and append to history.
     fn set_message(&mut self, msg: String, level: UiLogLevel, timeout: Option<u64>) {
+        self.history.push_back(LogEntry {
+            time: Instant::now(),
+            message: msg.clone(),
+            level,
+        });
+        while self.history.len() > LOG_HISTORY_CAPACITY {
+            self.history.pop_front();
+        }
         self.message = Some((msg, level));
         self.message_time = Some(Instant::now());
         self.timeout_override = timeout;
@@ -73,7 +95,9 @@ impl UiLogger {
         self.set_message(msg, UiLogLevel::Info, timeout);
     }
 
-    /// Call this regularly (e.g., every UI frame) to clear old messages.
+    /// Call this regularly (e.g., every UI frame) to clear the expired
+    /// toast. The `history` ring buffer is unaffected: it only trims on
+    /// capacity, not time.
     pub fn clear_expired(&mut self) {
         if let Some(t) = self.message_time {
             let timeout = self.timeout_override.unwrap_or(LOG_TIMEOUT as u64);
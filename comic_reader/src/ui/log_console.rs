@@ -0,0 +1,94 @@
+//! Global `log::Log` ring buffer feeding the in-app log console in the
+//! Debug Info window, so `debug!`/`trace!` calls sprinkled through
+//! `TextureCache` and elsewhere are visible from the running GUI without
+//! attaching a terminal.
+
+use std::collections::VecDeque;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use log::{Level, Log, Metadata, Record};
+
+use crate::prelude::*;
+
+/// Ring buffer capacity; oldest records are dropped once full.
+const CAPACITY: usize = 2000;
+
+/// One captured log line.
+#[derive(Clone)]
+pub struct LogRecord {
+    pub time: Instant,
+    pub level: Level,
+    pub target: String,
+    pub message: String,
+}
+
+pub type LogBuffer = Arc<Mutex<VecDeque<LogRecord>>>;
+
+static BUFFER: OnceLock<LogBuffer> = OnceLock::new();
+
+/// `log::Log` impl that forwards every record to `inner` (so console/file
+/// output from `env_logger` is unchanged) and also appends it to the
+/// shared ring buffer `display_log_console` reads from.
+struct RingBufferLogger {
+    inner: env_logger::Logger,
+    buffer: LogBuffer,
+}
+
+impl Log for RingBufferLogger {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.inner.enabled(metadata)
+    }
+
+    fn log(&self, record: &Record) {
+        if !self.inner.enabled(record.metadata()) {
+            return;
+        }
+        self.inner.log(record);
+
+        let mut buffer = self.buffer.lock().unwrap();
+        buffer.push_back(LogRecord {
+            time: Instant::now(),
+            level: record.level(),
+            target: record.target().to_string(),
+            message: format!("{}", record.args()),
+        });
+        while buffer.len() > CAPACITY {
+            buffer.pop_front();
+        }
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+}
+
+/// Install the ring-buffer logger as the global `log` backend in place of
+/// a plain `env_logger::init()`, and return the shared buffer for
+/// `display_debug_menu` to render. Must be called once, before any other
+/// `log` calls, typically at the top of `main`.
+pub fn init() -> LogBuffer {
+    let buffer: LogBuffer = Arc::new(Mutex::new(VecDeque::new()));
+    BUFFER.set(buffer.clone()).ok();
+
+    let env_logger = env_logger::Builder::from_default_env()
+        .format_timestamp_secs()
+        .build();
+    let max_level = env_logger.filter();
+    log::set_boxed_logger(Box::new(RingBufferLogger {
+        inner: env_logger,
+        buffer: buffer.clone(),
+    }))
+    .expect("logger already installed");
+    log::set_max_level(max_level);
+
+    buffer
+}
+
+/// The buffer installed by `init`, for code that doesn't already have a
+/// handle to it (e.g. a `CBZViewerApp` constructed after `init` ran).
+pub fn buffer() -> LogBuffer {
+    BUFFER
+        .get()
+        .cloned()
+        .unwrap_or_else(|| Arc::new(Mutex::new(VecDeque::new())))
+}
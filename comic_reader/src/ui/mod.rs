@@ -4,7 +4,12 @@ pub mod image;
 // pub mod layout;
 pub mod display;
 pub mod log;
+pub mod file_browser;
+pub mod library;
+pub mod log_console;
 pub mod modules;
+pub mod recents_panel;
+pub mod settings_modal;
 
 pub use image::*;
 // pub use layout::*;
@@ -42,15 +42,17 @@ pub fn ui_page_nav(app: &mut CBZViewerApp, ui: &mut Ui, total_pages: usize) {
     ui.label(page_label);
 }
 
-pub fn ui_log_msg(app: &mut CBZViewerApp, ui: &mut Ui) {
-    if let Some((msg, kind)) = &app.ui_logger.message {
-        ui.colored_label(kind.color(), format!("{}: {}", kind.as_str(), msg.clone()));
-    }
+/// Draw the toast for the most recent log message at its transient level
+/// color. The full history survives past this regardless of `msg`'s
+/// timeout; see `display_log_history`.
+pub fn ui_log_msg(ui: &mut Ui, msg: &str, kind: UiLogLevel) {
+    ui.colored_label(kind.color(), format!("{}: {}", kind.as_str(), msg));
 }
 
 pub async fn ui_file(app: &mut CBZViewerApp, ui: &mut Ui, _ctx: &Context) {
     // Temporary variable to track if we need to save the image after the menu closure
     let mut save_image_requested = false;
+    let mut make_offline_requested = false;
 
     ui.menu_button("File", |ui| {
         if ui.button("New Comic...").clicked() {
@@ -65,18 +67,63 @@ pub async fn ui_file(app: &mut CBZViewerApp, ui: &mut Ui, _ctx: &Context) {
             app.on_open_folder = true;
             ui.close_menu();
         }
+        ui.menu_button("Open Recent", |ui| {
+            if app.recents.entries.is_empty() {
+                ui.label("No recent archives");
+            }
+            for entry in app.recents.entries.clone() {
+                let label = entry
+                    .path
+                    .file_name()
+                    .map(|n| n.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| entry.path.to_string_lossy().into_owned());
+                if ui.button(label).clicked() {
+                    app.new_page = Some(entry.path);
+                    ui.close_menu();
+                }
+            }
+        });
+        if ui.button("Recent Files Panel...").clicked() {
+            app.show_recents_panel = true;
+            ui.close_menu();
+        }
+        if ui.button("File Browser...").clicked() {
+            app.show_file_browser = true;
+            ui.close_menu();
+        }
+        if ui.button("Browse Library...").clicked() {
+            app.on_open_library = true;
+            ui.close_menu();
+        }
         if ui.button("Save Image").clicked() {
             save_image_requested = true;
             ui.close_menu();
         }
+        if app.is_web_archive && ui.button("Download All (Make Offline)...").clicked() {
+            make_offline_requested = true;
+            ui.close_menu();
+        }
         if ui.button("Reload...").clicked() {
             if let Some(path) = app.archive_path.clone() {
                 let _ = app.load_new_file(path);
             } else {
-                app.ui_logger.warn("Failed to reload", None);
+                app.ui_logger.lock().unwrap().warn("Failed to reload", None);
             }
             ui.close_menu();
         }
+        if ui
+            .checkbox(&mut app.watch_enabled, "Watch for changes")
+            .on_hover_text("Automatically reload when the open archive changes on disk. Disable for archives on slow/remote storage.")
+            .changed()
+        {
+            if app.watch_enabled {
+                if let Some(path) = &app.archive_path {
+                    app.watcher = crate::watch::ArchiveWatcher::new(path);
+                }
+            } else {
+                app.watcher = None;
+            }
+        }
     });
 
     // Handle the async image saving outside the closure
@@ -98,24 +145,61 @@ pub async fn ui_file(app: &mut CBZViewerApp, ui: &mut Ui, _ctx: &Context) {
                         match tokio::fs::File::create(&save_path).await {
                             Ok(mut file) => {
                                 if let Err(e) = file.write(&image).await {
-                                    app.ui_logger.error(format!("Failed to save image: {}", e), None);
+                                    app.ui_logger
+                                        .lock()
+                                        .unwrap()
+                                        .error(format!("Failed to save image: {}", e), None);
                                 }
                             }
                             Err(e) => {
-                                app.ui_logger.error(format!("Failed to save image: {}", e), None);
+                                app.ui_logger
+                                    .lock()
+                                    .unwrap()
+                                    .error(format!("Failed to save image: {}", e), None);
                             }
                         }
                     } else {
-                        app.ui_logger.warn("No file selected for saving", None);
-                        
+                        app.ui_logger.lock().unwrap().warn("No file selected for saving", None);
+
                     }
 
                 } else {
-                    app.ui_logger.warn("No image to save", None);
+                    app.ui_logger.lock().unwrap().warn("No image to save", None);
                 }
             }
         }
     }
+
+    // Handle the "make offline" download outside the closure, same as saving
+    // a single image above.
+    if make_offline_requested {
+        if let Some(archive_mutex) = app.archive.clone() {
+            if let Some(save_path) = rfd::FileDialog::new()
+                .set_title("Make Offline Copy")
+                .set_file_name("offline.cbz")
+                .add_filter("Comic Book Zip", &["cbz"])
+                .save_file()
+            {
+                let result = tokio::task::spawn_blocking(move || {
+                    let mut archive = archive_mutex.lock().unwrap();
+                    archive.materialize_offline(&save_path)
+                })
+                .await
+                .unwrap();
+                if let Err(e) = result {
+                    app.ui_logger
+                        .lock()
+                        .unwrap()
+                        .error(format!("Failed to make offline copy: {}", e), None);
+                }
+            } else {
+                app.ui_logger
+                    .lock()
+                    .unwrap()
+                    .warn("No file selected for offline copy", None);
+            }
+        }
+    }
 }
 
 pub fn ui_edit(app: &mut CBZViewerApp, ui: &mut Ui, _ctx: &Context) {
@@ -124,6 +208,31 @@ pub fn ui_edit(app: &mut CBZViewerApp, ui: &mut Ui, _ctx: &Context) {
             app.show_manifest_editor = true;
             ui.close_menu();
         }
+        if ui.button("Settings...").clicked() {
+            app.show_settings_modal = true;
+            ui.close_menu();
+        }
+    });
+}
+
+pub fn ui_debug(app: &mut CBZViewerApp, ui: &mut Ui, _ctx: &Context) {
+    ui.menu_button("Debug", |ui| {
+        if ui.button("Debug Info...").clicked() {
+            app.show_debug_menu = true;
+            ui.close_menu();
+        }
+        if ui.button("Log History...").clicked() {
+            app.show_log_history = true;
+            ui.close_menu();
+        }
+        #[cfg(feature = "turbo")]
+        if ui
+            .checkbox(&mut app.use_turbo_jpeg, "Use TurboJPEG decoding")
+            .on_hover_text("Faster JPEG decoding via libjpeg-turbo. Disable to fall back to the pure-Rust decoder.")
+            .changed()
+        {
+            comic_archive::decode::set_turbo_enabled(app.use_turbo_jpeg);
+        }
     });
 }
 
@@ -167,9 +276,40 @@ pub fn ui_navigation(app: &mut CBZViewerApp, ui: &mut Ui) {
         .clicked()
     {
         app.show_thumbnail_grid = !app.show_thumbnail_grid;
+        if app.show_thumbnail_grid {
+            app.selected_thumb = app.current_page;
+        }
         // app.texture_cache.clear();
     }
 
+    if ui
+        .selectable_label(app.continuous_scroll_mode, "\u{f103}")
+        .on_hover_text("Continuous scroll (webtoon) mode")
+        .clicked()
+    {
+        app.continuous_scroll_mode = !app.continuous_scroll_mode;
+        app.has_initialised_zoom = false;
+        app.texture_cache.clear();
+        app.scroll_offset = 0.0;
+        app.page_heights.clear();
+    }
+
+    let is_bookmarked = app.bookmarks.iter().any(|b| b.page == app.current_page);
+    if ui
+        .selectable_label(is_bookmarked, "\u{f02e}")
+        .on_hover_text("Bookmark this page")
+        .clicked()
+    {
+        app.toggle_bookmark(app.current_page);
+    }
+    if ui
+        .selectable_label(app.show_bookmarks_popup, "\u{f0ca}")
+        .on_hover_text("Bookmarks")
+        .clicked()
+    {
+        app.show_bookmarks_popup = !app.show_bookmarks_popup;
+    }
+
     if app.double_page_mode {
         if ui
             .button("\u{f08e}")
@@ -0,0 +1,91 @@
+//! Recent-archives panel: lists `CBZViewerApp::recents`' entries with a
+//! thumbnail pulled from `comic_archive::thumbnail_cache` where available,
+//! and lets the user reopen one via `new_page` (the same "open this path"
+//! channel used by drag-and-drop and the file dialogs).
+
+use crate::prelude::*;
+
+impl CBZViewerApp {
+    pub fn display_recents_panel(&mut self, ctx: &egui::Context) {
+        if !self.show_recents_panel {
+            return;
+        }
+        let mut show = self.show_recents_panel;
+        let mut open_path = None;
+
+        egui::Window::new(
+            RichText::new("\u{f1da} Recent Files")
+                .color(Color32::from_rgb(0, 200, 160))
+                .heading(),
+        )
+        .open(&mut show)
+        .resizable(true)
+        .vscroll(true)
+        .show(ctx, |ui| {
+            if self.recents.entries.is_empty() {
+                ui.label("No recently opened archives yet.");
+                return;
+            }
+
+            for entry in self.recents.entries.clone() {
+                ui.horizontal(|ui| {
+                    let texture = entry.thumb_digest.as_ref().and_then(|digest| {
+                        if let Some(handle) = self.recents_textures.get(&entry.path) {
+                            return Some(handle.clone());
+                        }
+                        let bytes = comic_archive::thumbnail_cache::read(
+                            digest,
+                            RECENT_THUMB_SIZE,
+                            RECENT_THUMB_QUALITY,
+                        )?;
+                        let image = image::load_from_memory(&bytes).ok()?;
+                        let color_image = egui::ColorImage::from_rgba_unmultiplied(
+                            [image.width() as usize, image.height() as usize],
+                            &image.to_rgba8(),
+                        );
+                        let handle = ctx.load_texture(
+                            format!("recent_thumb_{}", entry.path.display()),
+                            color_image,
+                            egui::TextureOptions::default(),
+                        );
+                        self.recents_textures.insert(entry.path.clone(), handle.clone());
+                        Some(handle)
+                    });
+
+                    if let Some(texture) = texture {
+                        ui.add(
+                            egui::Image::new(&texture)
+                                .fit_to_exact_size(Vec2::splat(RECENT_THUMB_SIZE as f32)),
+                        );
+                    } else {
+                        ui.add_sized([RECENT_THUMB_SIZE as f32; 2], egui::Spinner::new());
+                    }
+
+                    ui.vertical(|ui| {
+                        let name = entry
+                            .path
+                            .file_name()
+                            .map(|n| n.to_string_lossy().into_owned())
+                            .unwrap_or_else(|| entry.path.to_string_lossy().into_owned());
+                        ui.label(RichText::new(name).strong());
+                        ui.label(
+                            RichText::new(entry.path.to_string_lossy())
+                                .small()
+                                .color(Color32::GRAY),
+                        );
+                        if ui.button("Open").clicked() {
+                            open_path = Some(entry.path.clone());
+                        }
+                    });
+                });
+                ui.separator();
+            }
+        });
+
+        self.show_recents_panel = show;
+        if let Some(path) = open_path {
+            self.new_page = Some(path);
+            self.show_recents_panel = false;
+        }
+    }
+}
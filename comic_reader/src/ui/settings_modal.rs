@@ -0,0 +1,76 @@
+//! Settings modal for the runtime-adjustable values in `config::Settings`,
+//! opened via the "Edit" menu's "Settings..." button.
+
+use crate::prelude::*;
+
+impl CBZViewerApp {
+    pub fn display_settings_modal(&mut self, ctx: &egui::Context) {
+        if !self.show_settings_modal {
+            return;
+        }
+
+        let mut open = self.show_settings_modal;
+        let mut cache_size_changed = false;
+        let mut view_invalidated = false;
+
+        egui::Window::new("Settings").open(&mut open).show(ctx, |ui| {
+            let cache_mb = self.settings.cache_memory_budget_bytes / (1024 * 1024);
+            let mut cache_mb = cache_mb;
+            if ui
+                .add(egui::Slider::new(&mut cache_mb, 32..=2048).text("Image cache budget (MB)"))
+                .changed()
+            {
+                self.settings.cache_memory_budget_bytes = cache_mb * 1024 * 1024;
+                cache_size_changed = true;
+            }
+
+            ui.add(egui::Slider::new(&mut self.settings.read_ahead, 1..=64).text("Read-ahead pages"));
+            ui.add(
+                egui::Slider::new(&mut self.settings.read_ahead_web, 1..=32)
+                    .text("Read-ahead pages (web archives)"),
+            );
+
+            if ui
+                .add(
+                    egui::Slider::new(&mut self.settings.page_margin_size, 0..=64)
+                        .text("Inter-page margin"),
+                )
+                .changed()
+            {
+                view_invalidated = true;
+            }
+
+            if ui
+                .checkbox(&mut self.settings.dual_page_mode, "Default to dual-page mode")
+                .changed()
+            {
+                view_invalidated = true;
+            }
+            if ui
+                .checkbox(
+                    &mut self.settings.right_to_left,
+                    "Default reading direction: right-to-left",
+                )
+                .changed()
+            {
+                view_invalidated = true;
+            }
+
+            ui.separator();
+            if ui.button("Save").clicked() {
+                self.settings.save();
+            }
+        });
+        self.show_settings_modal = open;
+
+        if cache_size_changed {
+            self.image_lru
+                .lock()
+                .unwrap()
+                .set_budget_bytes(self.settings.cache_memory_budget_bytes);
+        }
+        if view_invalidated {
+            self.texture_cache.clear();
+        }
+    }
+}
@@ -1,5 +1,149 @@
 use crate::prelude::*;
 
+/// How `display_thumbnail_grid` fits a decoded page into its square cell.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum ThumbFit {
+    /// Crop the centered `min(w, h)` square, then resize to fill the cell.
+    /// Gives a tidy uniform grid at the cost of trimming page edges.
+    Crop,
+    /// Resize preserving aspect ratio, letterboxed within the cell. Shows
+    /// the whole page but leaves padding on non-square art.
+    Fit,
+}
+
+pub const DEFAULT_THUMB_FIT: ThumbFit = ThumbFit::Crop;
+
+/// Resize a decoded page into a `thumb_size x thumb_size` thumbnail
+/// according to `fit`, instead of `resize_exact`'s forced (and distorting)
+/// stretch to a square.
+fn make_thumbnail(img: &DynamicImage, thumb_size: u32, fit: ThumbFit) -> DynamicImage {
+    match fit {
+        ThumbFit::Crop => {
+            let (w, h) = img.dimensions();
+            let smaller = w.min(h);
+            let x = (w - smaller) / 2;
+            let y = (h - smaller) / 2;
+            img.crop_imm(x, y, smaller, smaller).resize_exact(
+                thumb_size,
+                thumb_size,
+                image::imageops::FilterType::Lanczos3,
+            )
+        }
+        ThumbFit::Fit => {
+            let resized = img.resize(
+                thumb_size,
+                thumb_size,
+                image::imageops::FilterType::Lanczos3,
+            );
+            let (rw, rh) = resized.dimensions();
+            let mut canvas =
+                image::RgbaImage::from_pixel(thumb_size, thumb_size, image::Rgba([0, 0, 0, 0]));
+            let x = (thumb_size - rw) / 2;
+            let y = (thumb_size - rh) / 2;
+            image::imageops::overlay(&mut canvas, &resized.to_rgba8(), x as i64, y as i64);
+            DynamicImage::ImageRgba8(canvas)
+        }
+    }
+}
+
+/// A thumbnail-grid cell's decoded (and already thumb-sized) contents.
+/// Separate from `PageImage` since thumbnails never need the full decoder
+/// machinery (tiling, SVG re-rasterization) the main viewer does.
+pub enum ThumbImage {
+    Static(DynamicImage),
+    /// An animated page's full frame sequence, thumb-sized, so hovering a
+    /// cell can play it instead of freezing on frame 0.
+    Animated {
+        frames: Vec<DynamicImage>,
+        delays: Vec<u16>,
+        start_time: std::time::Instant,
+    },
+}
+
+impl ThumbImage {
+    pub fn first_frame(&self) -> &DynamicImage {
+        match self {
+            ThumbImage::Static(img) => img,
+            ThumbImage::Animated { frames, .. } => &frames[0],
+        }
+    }
+
+    pub fn approx_bytes(&self) -> usize {
+        match self {
+            ThumbImage::Static(img) => img.as_bytes().len(),
+            ThumbImage::Animated { frames, .. } => frames.iter().map(|f| f.as_bytes().len()).sum(),
+        }
+    }
+}
+
+/// An un-thumbnailed decode result, still at whatever resolution the
+/// source codec produced. Kept separate from `ThumbImage` so the
+/// `is_web_archive` full-resolution LRU population path (which only makes
+/// sense for the static case) can run before the thumbnail resize throws
+/// that resolution away.
+enum DecodedThumbSource {
+    Static(DynamicImage),
+    Animated(Vec<DynamicImage>, Vec<u16>),
+}
+
+/// Decode `data` (named `name`, for extension-based format dispatch) into
+/// its full frame sequence when animated, or a single full-resolution
+/// image otherwise. Reuses the same codec dispatch as the main viewer
+/// (`comic_archive::decode`), so any format it can open, thumbnails can too.
+fn decode_thumb_source(name: &str, data: &[u8]) -> Option<DecodedThumbSource> {
+    if let Ok(frames) = comic_archive::decode::decode_frames(name, data) {
+        if frames.len() > 1 {
+            let mut imgs = Vec::with_capacity(frames.len());
+            let mut delays = Vec::with_capacity(frames.len());
+            for frame in frames {
+                let img = image::RgbaImage::from_raw(frame.width, frame.height, frame.rgba)
+                    .map(DynamicImage::ImageRgba8)?;
+                imgs.push(img);
+                delays.push(frame.delay_ms.max(20));
+            }
+            return Some(DecodedThumbSource::Animated(imgs, delays));
+        }
+    }
+    comic_archive::decode::decode_first_frame(name, data)
+        .ok()
+        .map(DecodedThumbSource::Static)
+}
+
+/// Resize a decoded source down to thumbnail size, frame by frame for an
+/// animated source.
+fn make_thumb_image(source: DecodedThumbSource, thumb_size: u32, fit: ThumbFit) -> ThumbImage {
+    match source {
+        DecodedThumbSource::Static(img) => ThumbImage::Static(make_thumbnail(&img, thumb_size, fit)),
+        DecodedThumbSource::Animated(imgs, delays) => ThumbImage::Animated {
+            frames: imgs
+                .iter()
+                .map(|img| make_thumbnail(img, thumb_size, fit))
+                .collect(),
+            delays,
+            start_time: std::time::Instant::now(),
+        },
+    }
+}
+
+/// Which frame of an animated thumbnail is showing `elapsed` time since
+/// `start_time`, and how many milliseconds until the next frame change
+/// (for scheduling the repaint that keeps it playing while hovered).
+fn animated_frame_state(start_time: std::time::Instant, delays: &[u16]) -> (usize, u64) {
+    let total: u64 = delays.iter().map(|d| *d as u64).sum();
+    if total == 0 {
+        return (0, 100);
+    }
+    let t = start_time.elapsed().as_millis() as u64 % total;
+    let mut acc = 0u64;
+    for (i, delay) in delays.iter().enumerate() {
+        acc += *delay as u64;
+        if t < acc {
+            return (i, (acc - t).max(1));
+        }
+    }
+    (delays.len() - 1, 100)
+}
+
 impl CBZViewerApp {
     pub fn display_thumbnail_grid(&mut self, ctx: &egui::Context) {
         egui::CentralPanel::default().show(ctx, |ui| {
@@ -10,6 +154,24 @@ impl CBZViewerApp {
 
             ui.add_space(edge_margin); // Top margin
 
+            ui.horizontal(|ui| {
+                ui.add_space(edge_margin);
+                ui.label("Thumbnails:");
+                if ui
+                    .selectable_label(self.thumb_fit == ThumbFit::Crop, "Crop")
+                    .clicked()
+                {
+                    self.thumb_fit = ThumbFit::Crop;
+                }
+                if ui
+                    .selectable_label(self.thumb_fit == ThumbFit::Fit, "Fit")
+                    .clicked()
+                {
+                    self.thumb_fit = ThumbFit::Fit;
+                }
+            });
+            ui.add_space(border);
+
             let thumb_size =
                 ((available_width - (columns as f32 + 1.0) * border - 2.0 * edge_margin)
                     / columns as f32)
@@ -17,7 +179,55 @@ impl CBZViewerApp {
 
             let total = self.total_pages;
 
-            egui::ScrollArea::vertical().show(ui, |ui| {
+            if total > 0 {
+                self.selected_thumb = self.selected_thumb.min(total - 1);
+                let visible_rows =
+                    ((ui.available_height() / (thumb_size as f32 + border)).floor() as usize).max(1);
+                let page_step = visible_rows * columns;
+
+                let mut selected = self.selected_thumb;
+                let mut open_selected = false;
+                ctx.input(|i| {
+                    if i.key_pressed(egui::Key::ArrowRight) {
+                        selected = (selected + 1).min(total - 1);
+                    }
+                    if i.key_pressed(egui::Key::ArrowLeft) {
+                        selected = selected.saturating_sub(1);
+                    }
+                    if i.key_pressed(egui::Key::ArrowDown) {
+                        selected = (selected + columns).min(total - 1);
+                    }
+                    if i.key_pressed(egui::Key::ArrowUp) {
+                        selected = selected.saturating_sub(columns);
+                    }
+                    if i.key_pressed(egui::Key::Home) {
+                        selected = 0;
+                    }
+                    if i.key_pressed(egui::Key::End) {
+                        selected = total - 1;
+                    }
+                    if i.key_pressed(egui::Key::PageDown) {
+                        selected = (selected + page_step).min(total - 1);
+                    }
+                    if i.key_pressed(egui::Key::PageUp) {
+                        selected = selected.saturating_sub(page_step);
+                    }
+                    if i.key_pressed(egui::Key::Enter) || i.key_pressed(egui::Key::Space) {
+                        open_selected = true;
+                    }
+                });
+                self.selected_thumb = selected;
+
+                if open_selected {
+                    self.goto_page(self.selected_thumb);
+                    self.show_thumbnail_grid = false;
+                }
+            }
+
+            let prev_scroll_y = self.thumb_grid_scroll_offset;
+            let scroll_velocity = self.thumb_grid_scroll_velocity;
+
+            let scroll_output = egui::ScrollArea::vertical().show(ui, |ui| {
                 let mut idx = 0;
                 let mut closed_by_user = false;
 
@@ -31,9 +241,33 @@ impl CBZViewerApp {
                             }
                             let rect =
                                 ui.allocate_space(egui::vec2(thumb_size as f32, thumb_size as f32));
+                            if page_idx == self.selected_thumb {
+                                ui.scroll_to_rect(rect.1, None);
+                            }
+                            // Prefetch ring: also decode cells within
+                            // `DEFAULT_THUMB_PREFETCH_SCREENS` screenfuls of
+                            // the viewport, extended further in the direction
+                            // of travel so fast scrolling has thumbnails
+                            // ready before the cell comes on screen instead
+                            // of showing a wall of spinners.
+                            let in_prefetch_ring = {
+                                let viewport = ui.clip_rect();
+                                let screenful = viewport.height().max(1.0);
+                                let base = screenful * DEFAULT_THUMB_PREFETCH_SCREENS;
+                                let velocity_bonus = screenful * (scroll_velocity.abs() / screenful).min(1.0);
+                                let margin_up = base + if scroll_velocity < 0.0 { velocity_bonus } else { 0.0 };
+                                let margin_down = base + if scroll_velocity > 0.0 { velocity_bonus } else { 0.0 };
+                                let prefetch_rect = egui::Rect::from_min_max(
+                                    egui::pos2(viewport.min.x, viewport.min.y - margin_up),
+                                    egui::pos2(viewport.max.x, viewport.max.y + margin_down),
+                                );
+                                prefetch_rect.intersects(rect.1)
+                            };
+
                             let resp = {
-                                // Only generate if visible and not already cached
-                                if ui.is_rect_visible(rect.1)
+                                // Only generate if visible (or within the
+                                // prefetch ring) and not already cached
+                                if in_prefetch_ring
                                     && !self.thumbnail_cache.lock().unwrap().contains_key(&page_idx)
                                 {
                                     let archive = self.archive.clone();
@@ -41,6 +275,7 @@ impl CBZViewerApp {
                                     let semaphore = self.thumb_semaphore.clone();
                                     let page_idx_copy = page_idx;
                                     let thumb_size_copy = thumb_size;
+                                    let thumb_fit = self.thumb_fit;
                                     let is_web_archive = self.is_web_archive;
                                     let image_lru = self.image_lru.clone();
 
@@ -63,54 +298,35 @@ impl CBZViewerApp {
                                             };
 
                                             if let Ok(img_data) = img_data {
-                                                // Detect GIF by magic bytes
-                                                let is_gif = img_data.starts_with(b"GIF87a")
-                                                    || img_data.starts_with(b"GIF89a");
-                                                let img_result = if is_gif {
-                                                    use image::AnimationDecoder;
-                                                    use image::codecs::gif::GifDecoder;
-                                                    use std::io::Cursor;
-                                                    let cursor = Cursor::new(&*img_data);
-                                                    if let Ok(decoder) = GifDecoder::new(cursor) {
-                                                        if let Ok(frames) =
-                                                            decoder.into_frames().collect_frames()
-                                                        {
-                                                            if let Some(frame) = frames.get(0) {
-                                                                Some(image::DynamicImage::from(
-                                                                    frame.clone().into_buffer(),
-                                                                ))
-                                                            } else {
-                                                                None
-                                                            }
-                                                        } else {
-                                                            None
-                                                        }
-                                                    } else {
-                                                        None
-                                                    }
-                                                } else {
-                                                    image::load_from_memory(&img_data).ok()
-                                                };
+                                                // Shared with the main viewer's decode path, so
+                                                // AVIF/HEIF/RAW/SVG pages get a real thumbnail
+                                                // instead of falling back to a spinner, and
+                                                // animated GIF/WebP pages keep their full frame
+                                                // sequence so the grid can play them on hover.
+                                                let source = decode_thumb_source(&filename, &img_data);
                                                 let filename_clone = filename.clone();
                                                 let is_web_archive = is_web_archive;
                                                 let image_lru = image_lru.clone();
-                                                if let Some(img) = img_result {
-                                                    // If this is a webarchive, add to LRU cache
+                                                if let Some(source) = source {
+                                                    // If this is a webarchive, add the full-res
+                                                    // static decode to the LRU cache (animated
+                                                    // pages are handled by the main viewer's own
+                                                    // decode path when opened, not here).
                                                     if is_web_archive {
-                                                        use crate::cache::image_cache::PageImage;
-                                                        let mut lru = image_lru.lock().unwrap();
-                                                        lru.put(page_idx_copy, crate::cache::image_cache::LoadedPage {
-                                                            image: PageImage::Static(img.clone()),
-                                                            filename: filename_clone,
-                                                            index: page_idx_copy.clone(),
-                                                        });
+                                                        if let DecodedThumbSource::Static(ref img) = source {
+                                                            use crate::cache::image_cache::PageImage;
+                                                            let mut lru = image_lru.lock().unwrap();
+                                                            lru.put(page_idx_copy, crate::cache::image_cache::LoadedPage {
+                                                                image: PageImage::Static(img.clone()),
+                                                                filename: filename_clone,
+                                                                index: page_idx_copy.clone(),
+                                                                downsampled: false,
+                                                            });
+                                                        }
                                                     }
                                                     // Always resize to thumbnail size before caching
-                                                    let thumb = img.resize_exact(
-                                                        thumb_size_copy,
-                                                        thumb_size_copy,
-                                                        image::imageops::FilterType::Lanczos3,
-                                                    );
+                                                    let thumb =
+                                                        make_thumb_image(source, thumb_size_copy, thumb_fit);
                                                     let mut cache_guard = cache.lock().unwrap();
                                                     cache_guard.insert(page_idx_copy, thumb);
                                                 }
@@ -123,14 +339,17 @@ impl CBZViewerApp {
                                         // Try LRU cache first
                                         if let Some(lru_entry) = self.image_lru.lock().unwrap().get(&page_idx) {
                                             if let PageImage::Static(ref dyn_img) = lru_entry.image {
-                                                let thumb = dyn_img.resize_exact(
-                                                    thumb_size,
+                                                let thumb = ThumbImage::Static(make_thumbnail(
+                                                    dyn_img,
                                                     thumb_size,
-                                                    image::imageops::FilterType::Lanczos3,
-                                                );
+                                                    self.thumb_fit,
+                                                ));
                                                 self.thumbnail_cache.lock().unwrap().insert(page_idx, thumb);
                                             } else {
-                                                // If it's not a static image, skip or handle other variants as needed
+                                                // Animated pages in the LRU only hold already-
+                                                // uploaded GPU textures, not CPU pixels, so
+                                                // there's nothing to rebuild a `ThumbImage` from
+                                                // here; fall through to the async re-decode below.
                                             }
                                         } else {
                                             let archive = self.archive.clone();
@@ -138,6 +357,7 @@ impl CBZViewerApp {
                                             let semaphore = self.thumb_semaphore.clone();
                                             let page_idx_copy = page_idx;
                                             let thumb_size_copy = thumb_size;
+                                            let thumb_fit = self.thumb_fit;
 
                                             // Clone the filename while holding the lock, then drop the guard before spawn
                                             let filename = {
@@ -158,41 +378,17 @@ impl CBZViewerApp {
                                                     };
 
                                                     if let Ok(img_data) = img_data {
-                                                        // Detect GIF by magic bytes
-                                                        let is_gif = img_data.starts_with(b"GIF87a")
-                                                            || img_data.starts_with(b"GIF89a");
-                                                        let img_result = if is_gif {
-                                                            use image::AnimationDecoder;
-                                                            use image::codecs::gif::GifDecoder;
-                                                            use std::io::Cursor;
-                                                            let cursor = Cursor::new(&*img_data);
-                                                            if let Ok(decoder) = GifDecoder::new(cursor) {
-                                                                if let Ok(frames) =
-                                                                    decoder.into_frames().collect_frames()
-                                                                {
-                                                                    if let Some(frame) = frames.get(0) {
-                                                                        Some(image::DynamicImage::from(
-                                                                            frame.clone().into_buffer(),
-                                                                        ))
-                                                                    } else {
-                                                                        None
-                                                                    }
-                                                                } else {
-                                                                    None
-                                                                }
-                                                            } else {
-                                                                None
-                                                            }
-                                                        } else {
-                                                            image::load_from_memory(&img_data).ok()
-                                                        };
-
-                                                        if let Some(img) = img_result {
+                                                        // Shared with the main viewer's decode path
+                                                        // (see the sibling branch above).
+                                                        let source =
+                                                            decode_thumb_source(&filename, &img_data);
+
+                                                        if let Some(source) = source {
                                                             // Always resize to thumbnail size before caching
-                                                            let thumb = img.resize_exact(
-                                                                thumb_size_copy,
+                                                            let thumb = make_thumb_image(
+                                                                source,
                                                                 thumb_size_copy,
-                                                                image::imageops::FilterType::Lanczos3,
+                                                                thumb_fit,
                                                             );
                                                             let mut cache_guard = cache.lock().unwrap();
                                                             cache_guard.insert(page_idx_copy, thumb);
@@ -204,28 +400,72 @@ impl CBZViewerApp {
                                     }
                                 }
 
-                                // Always show spinner until the thumbnail is loaded
-                                if let Some(img) = self.thumbnail_cache.lock().unwrap().get(&page_idx) {
+                                // Hovering an animated thumbnail plays it; decide which
+                                // frame *before* placing the widget (`resp.hovered()`
+                                // would only reflect this frame's interaction after
+                                // placement, one frame too late to pick the texture).
+                                let hovered = ui.rect_contains_pointer(rect.1);
+                                let frame_idx = match self.thumbnail_cache.lock().unwrap().get(&page_idx) {
+                                    Some(ThumbImage::Animated { start_time, delays, .. }) if hovered => {
+                                        let (frame_idx, until_next_ms) =
+                                            animated_frame_state(*start_time, delays);
+                                        ui.ctx().request_repaint_after(
+                                            std::time::Duration::from_millis(until_next_ms),
+                                        );
+                                        frame_idx
+                                    }
+                                    _ => 0,
+                                };
+
+                                // Reuse the uploaded texture across frames instead of
+                                // re-encoding and re-uploading the same bitmap every
+                                // repaint; only decoded-but-not-yet-uploaded frames
+                                // pay the upload cost, once each.
+                                let tex = if let Some(handle) =
+                                    self.thumbnail_textures.get_frame(page_idx, frame_idx)
+                                {
+                                    Some(handle.clone())
+                                } else if let Some(thumb) = self.thumbnail_cache.lock().unwrap().get(&page_idx)
+                                {
+                                    let img = match thumb {
+                                        ThumbImage::Static(img) => img,
+                                        ThumbImage::Animated { frames, .. } => &frames[frame_idx],
+                                    };
                                     let color_img = egui::ColorImage::from_rgba_unmultiplied(
                                         [img.width() as usize, img.height() as usize],
                                         &img.to_rgba8(),
                                     );
-                                    let tex = ui.ctx().load_texture(
-                                        format!("thumb_{}", page_idx),
+                                    let handle = ui.ctx().load_texture(
+                                        format!("thumb_{}_{}", page_idx, frame_idx),
                                         color_img,
                                         egui::TextureOptions::default(),
                                     );
+                                    self.thumbnail_textures.set_frame(page_idx, frame_idx, handle.clone());
+                                    Some(handle)
+                                } else {
+                                    None
+                                };
+
+                                // Always show spinner until the thumbnail is loaded
+                                if let Some(tex) = tex {
                                     // Highlight border on hover
                                     let resp = ui.put(
                                         rect.1,
                                         egui::ImageButton::new(
                                             egui::Image::from_texture(&tex)
-                                                .fit_to_exact_size(egui::vec2(img.width() as f32, img.height() as f32))
+                                                .fit_to_exact_size(egui::vec2(thumb_size as f32, thumb_size as f32))
                                         )
                                         .frame(false)
                                         .sense(egui::Sense::click()),
                                     );
-                                    if resp.hovered() {
+                                    if page_idx == self.current_page {
+                                        ui.painter().rect_stroke(
+                                            rect.1,
+                                            6.0,
+                                            egui::Stroke::new(3.0, egui::Color32::GOLD),
+                                            egui::StrokeKind::Outside,
+                                        );
+                                    } else if resp.hovered() || page_idx == self.selected_thumb {
                                         let stroke =
                                             egui::Stroke::new(3.0, egui::Color32::LIGHT_BLUE);
                                         ui.painter().rect_stroke(
@@ -260,10 +500,9 @@ impl CBZViewerApp {
                                         egui::Color32::WHITE,
                                     );
                                     if resp.clicked() {
-                                        self.current_page = page_idx;
+                                        self.selected_thumb = page_idx;
+                                        self.goto_page(page_idx);
                                         closed_by_user = true;
-                                        // Defer on_page_changed to after the closure to avoid borrowing issues
-                                        // self.on_page_changed();
                                     }
                                     ui.add_space(border);
                                 } else {
@@ -279,9 +518,12 @@ impl CBZViewerApp {
                 }
                 if closed_by_user {
                     self.show_thumbnail_grid = false;
-                    self.on_page_changed();
                 }
             });
+
+            let new_scroll_y = scroll_output.state.offset.y;
+            self.thumb_grid_scroll_velocity = new_scroll_y - prev_scroll_y;
+            self.thumb_grid_scroll_offset = new_scroll_y;
         });
     }
 }
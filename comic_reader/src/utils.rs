@@ -3,9 +3,72 @@ use crate::prelude::*;
 use std::io::Write;
 use zip::{ZipWriter, write::FileOptions};
 
+/// Compression choices for writing or rebuilding a CBZ, split by entry kind
+/// since pages and the manifest compress very differently.
+///
+/// Page images (JPEG/PNG/WebP/...) are already compressed, so re-deflating
+/// them mostly burns CPU for little size gain — the default keeps
+/// `image_method` at `Stored`. `manifest.toml` is small, repetitive text
+/// that deflates well, so it defaults to `Deflated`. Both are independently
+/// overridable (e.g. to pick `Zstd` for pages in an archive full of PNGs).
+///
+/// `zstd_window_log` only applies when a method is `Zstd`: a bigger window
+/// (up to 26, i.e. 64 MiB) lets later pages reference earlier ones across a
+/// wider span, which shrinks multi-page archives further at the cost of
+/// more memory while reading and writing. `None` uses zstd's own default.
+#[derive(Debug, Clone, Copy)]
+pub struct CompressionSettings {
+    pub image_method: zip::CompressionMethod,
+    pub image_level: Option<i64>,
+    pub manifest_method: zip::CompressionMethod,
+    pub manifest_level: Option<i64>,
+    pub zstd_window_log: Option<i32>,
+}
+
+impl Default for CompressionSettings {
+    fn default() -> Self {
+        Self {
+            image_method: zip::CompressionMethod::Stored,
+            image_level: None,
+            manifest_method: zip::CompressionMethod::Deflated,
+            manifest_level: None,
+            zstd_window_log: None,
+        }
+    }
+}
+
+impl CompressionSettings {
+    fn image_options(&self) -> FileOptions {
+        self.options_for(self.image_method, self.image_level)
+    }
+
+    fn manifest_options(&self) -> FileOptions {
+        self.options_for(self.manifest_method, self.manifest_level)
+    }
+
+    fn options_for(&self, method: zip::CompressionMethod, level: Option<i64>) -> FileOptions {
+        let mut options = FileOptions::default()
+            .compression_method(method)
+            .unix_permissions(0o644);
+        if let Some(level) = level {
+            options = options.compression_level(Some(level));
+        }
+        if method == zip::CompressionMethod::Zstd {
+            if let Some(window_log) = self.zstd_window_log {
+                options = options.zstd_window_log(window_log);
+            }
+        }
+        options
+    }
+}
+
+/// Rebuild `original_path` with an updated `manifest.toml`, re-applying
+/// `settings` per entry (images keep their own method, the manifest gets
+/// its own) rather than compressing everything the same way.
 pub fn rebuild_zip_with_manifest(
     original_path: &Path,
     manifest: &Manifest,
+    settings: &CompressionSettings,
 ) -> Result<(), AppError> {
     // Open original archive
     let original_file = File::open(original_path)?;
@@ -16,10 +79,11 @@ pub fn rebuild_zip_with_manifest(
     let mut temp_file = File::create(&temp_path)?;
 
     let mut writer = ZipWriter::new(&mut temp_file);
-    let options =
-        zip::write::FileOptions::default().compression_method(zip::CompressionMethod::Stored);
+    let image_options = settings.image_options();
 
-    // Copy existing entries, skipping manifest.toml
+    // Copy existing entries, skipping manifest.toml. Each entry gets its
+    // own options so an already-Stored image isn't forced through the
+    // manifest's compression settings (or vice versa).
     for i in 0..zip.len() {
         let mut file = zip.by_index(i)?;
         let name = file.name().to_string();
@@ -28,12 +92,12 @@ pub fn rebuild_zip_with_manifest(
             continue;
         }
 
-        writer.start_file(name, options)?;
+        writer.start_file(name, image_options)?;
         std::io::copy(&mut file, &mut writer)?;
     }
 
-    // Write new manifest.toml
-    writer.start_file("manifest.toml", options)?;
+    // Write new manifest.toml with its own (typically Deflated) options.
+    writer.start_file("manifest.toml", settings.manifest_options())?;
     let toml = toml::to_string_pretty(manifest)
         .map_err(|e| AppError::ManifestError(format!("Invalid TOML: {}", e)))?;
     writer.write_all(toml.as_bytes())?;
@@ -45,21 +109,20 @@ pub fn rebuild_zip_with_manifest(
     Ok(())
 }
 
-pub fn create_cbz_with_manifest(path: &std::path::Path) -> Result<(), AppError> {
+pub fn create_cbz_with_manifest(
+    path: &std::path::Path,
+    settings: &CompressionSettings,
+) -> Result<(), AppError> {
     let file = File::create(path)?;
     let mut zip = ZipWriter::new(file);
 
-    let options = FileOptions::default()
-        .compression_method(zip::CompressionMethod::Stored) // or .Deflated
-        .unix_permissions(0o644);
-
     let mut manifest = Manifest::default();
     manifest.meta.web_archive = true;
 
     let manifest_str = toml::to_string_pretty(&manifest)
         .map_err(|e| AppError::ManifestError(format!("Couldn't serialize: {}", e)))?;
 
-    zip.start_file("manifest.toml", options)?;
+    zip.start_file("manifest.toml", settings.manifest_options())?;
     zip.write_all(manifest_str.as_bytes())?;
 
     zip.finish()?; // Closes the archive and flushes everything
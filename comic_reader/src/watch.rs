@@ -0,0 +1,54 @@
+//! Filesystem watching for live reload of the currently open archive/folder.
+
+use notify::{RecommendedWatcher, RecursiveMode, Watcher};
+use std::path::Path;
+use std::sync::mpsc::{Receiver, channel};
+use std::time::Duration;
+
+/// Debounce window: rapid bursts of events (e.g. a multi-file extraction)
+/// collapse into a single reload.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+
+/// A live watcher on the open archive path. Dropping this stops watching.
+pub struct ArchiveWatcher {
+    _watcher: RecommendedWatcher,
+    rx: Receiver<notify::Result<notify::Event>>,
+}
+
+impl ArchiveWatcher {
+    /// Watch `path` (a file or folder) for changes.
+    pub fn new(path: &Path) -> Option<Self> {
+        let (tx, rx) = channel();
+        let mut watcher = notify::recommended_watcher(move |res| {
+            let _ = tx.send(res);
+        })
+        .ok()?;
+
+        let mode = if path.is_dir() {
+            RecursiveMode::Recursive
+        } else {
+            RecursiveMode::NonRecursive
+        };
+        watcher.watch(path, mode).ok()?;
+
+        Some(Self { _watcher: watcher, rx })
+    }
+
+    /// Returns true if a change was observed since the last call, debouncing
+    /// bursts of events into a single signal.
+    pub fn poll_changed(&self) -> bool {
+        let mut changed = false;
+        while let Ok(res) = self.rx.try_recv() {
+            if res.is_ok() {
+                changed = true;
+            }
+        }
+        if changed {
+            // Drain anything that arrives within the debounce window too, so a
+            // burst of writes (zip rebuild, batch download) triggers one reload.
+            std::thread::sleep(DEBOUNCE);
+            while let Ok(_) = self.rx.try_recv() {}
+        }
+        changed
+    }
+}
@@ -2,9 +2,10 @@ use windows::core::*;
 use windows::Win32::System::Com::*;
 use windows::Win32::UI::Shell::*;
 use windows::Win32::Graphics::Gdi::*;
-use windows::Win32::Foundation::{S_OK, E_FAIL};
+use windows::Win32::Foundation::{S_OK, E_FAIL, CLASS_E_CLASSNOTAVAILABLE, CLASS_E_NOAGGREGATION, BOOL};
 use comic_archive::ImageArchive;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::cell::RefCell;
 use image::{DynamicImage, ImageFormat};
 use image::GenericImageView;
 use windows::core::GUID;
@@ -13,8 +14,46 @@ use windows::core::GUID;
 pub const CLSID_COMIC_THUMB_PROVIDER: GUID = GUID::from_u128(0x6e80958a_59b6_41b4_932b_64d3c9532235);
 pub const CLSID_COMIC_THUMB_PROVIDER_STR: &str = "{6e80958a-59b6-41b4-932b-64d3c9532235}";
 
-#[implement(IThumbnailProvider)]
-pub struct ComicThumbnailProvider;
+/// Where `IInitializeWithFile`/`IInitializeWithStream` stashed the item the
+/// shell asked us to thumbnail, so `GetThumbnail` has something real to
+/// read instead of a hardcoded path.
+enum ThumbSource {
+    File(PathBuf),
+    /// `ImageArchive::process` only reads from disk, so a stream (used for
+    /// virtual/network items with no local path) gets spooled to a temp
+    /// file first.
+    SpooledStream(PathBuf),
+}
+
+impl ThumbSource {
+    fn path(&self) -> &Path {
+        match self {
+            ThumbSource::File(p) => p,
+            ThumbSource::SpooledStream(p) => p,
+        }
+    }
+}
+
+impl Drop for ThumbSource {
+    fn drop(&mut self) {
+        if let ThumbSource::SpooledStream(p) = self {
+            let _ = std::fs::remove_file(p);
+        }
+    }
+}
+
+#[implement(IThumbnailProvider, IInitializeWithFile, IInitializeWithStream)]
+pub struct ComicThumbnailProvider {
+    source: RefCell<Option<ThumbSource>>,
+}
+
+impl ComicThumbnailProvider {
+    pub fn new() -> Self {
+        Self {
+            source: RefCell::new(None),
+        }
+    }
+}
 
 impl IThumbnailProvider_Impl for ComicThumbnailProvider {
     fn GetThumbnail(
@@ -23,8 +62,11 @@ impl IThumbnailProvider_Impl for ComicThumbnailProvider {
         phbmp: *mut HBITMAP,
         pdw_alpha: *mut WTS_ALPHATYPE,
     ) -> windows::core::Result<()> {
-        // TODO: Get the file path from the COM context (not shown here)
-        let archive_path = Path::new("B:/Explicit/EULA.cbz");
+        let guard = self.source.borrow();
+        let archive_path = guard
+            .as_ref()
+            .ok_or_else(|| windows::core::Error::from(E_FAIL))?
+            .path();
         let mut archive = ImageArchive::process(archive_path)
             .map_err(|_| windows::core::Error::from(E_FAIL))?;
         let image_list = archive.list_images();
@@ -34,7 +76,12 @@ impl IThumbnailProvider_Impl for ComicThumbnailProvider {
         let image_bytes = archive.read_image_by_name(&image_list[0])
             .map_err(|_| windows::core::Error::from(E_FAIL))?;
 
-        let img = image::load_from_memory(&image_bytes)
+        // Goes through the same decode path as the reader's page cache,
+        // including the turbojpeg scaled-IDCT fast path (`turbo` feature)
+        // for the common case of a JPEG-heavy archive, instead of always
+        // paying for a full-resolution `image` crate decode just to shrink
+        // it back down here.
+        let img = comic_archive::decode::decode_first_frame_scaled(&image_list[0], &image_bytes, cx)
             .map_err(|_| windows::core::Error::from(E_FAIL))?;
         let thumb = img.thumbnail(cx, cx);
         let rgba = thumb.to_rgba8();
@@ -63,6 +110,71 @@ impl IThumbnailProvider_Impl for ComicThumbnailProvider {
     }
 }
 
+impl IInitializeWithFile_Impl for ComicThumbnailProvider {
+    fn Initialize(&self, pszfilepath: &PCWSTR, _grfmode: u32) -> windows::core::Result<()> {
+        let path = unsafe { pszfilepath.to_string() }
+            .map_err(|_| windows::core::Error::from(E_FAIL))?;
+        *self.source.borrow_mut() = Some(ThumbSource::File(PathBuf::from(path)));
+        Ok(())
+    }
+}
+
+impl IInitializeWithStream_Impl for ComicThumbnailProvider {
+    fn Initialize(&self, pstream: Option<&IStream>, _grfmode: u32) -> windows::core::Result<()> {
+        let stream = pstream.ok_or_else(|| windows::core::Error::from(E_FAIL))?;
+
+        let mut buf = Vec::new();
+        let mut chunk = [0u8; 64 * 1024];
+        loop {
+            let mut read: u32 = 0;
+            unsafe {
+                stream
+                    .Read(chunk.as_mut_ptr() as *mut _, chunk.len() as u32, Some(&mut read))
+                    .map_err(|_| windows::core::Error::from(E_FAIL))?;
+            }
+            if read == 0 {
+                break;
+            }
+            buf.extend_from_slice(&chunk[..read as usize]);
+        }
+
+        let nanos = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0);
+        let temp_path = std::env::temp_dir().join(format!("comic_thumb_{:x}.tmp", nanos));
+        std::fs::write(&temp_path, &buf).map_err(|_| windows::core::Error::from(E_FAIL))?;
+
+        *self.source.borrow_mut() = Some(ThumbSource::SpooledStream(temp_path));
+        Ok(())
+    }
+}
+
+/// Class factory handed back by `DllGetClassObject` so the shell can
+/// actually instantiate a `ComicThumbnailProvider` for our registered
+/// CLSID, instead of the stub just failing every query.
+#[implement(IClassFactory)]
+struct ComicThumbProviderFactory;
+
+impl IClassFactory_Impl for ComicThumbProviderFactory {
+    fn CreateInstance(
+        &self,
+        outer: Option<&IUnknown>,
+        riid: *const GUID,
+        ppvobject: *mut *mut std::ffi::c_void,
+    ) -> windows::core::Result<()> {
+        if outer.is_some() {
+            return Err(windows::core::Error::from(CLASS_E_NOAGGREGATION));
+        }
+        let provider: IUnknown = ComicThumbnailProvider::new().into();
+        unsafe { provider.query(&*riid, ppvobject).ok() }
+    }
+
+    fn LockServer(&self, _flock: BOOL) -> windows::core::Result<()> {
+        Ok(())
+    }
+}
+
 // Required DLL exports for COM registration
 #[unsafe(no_mangle)]
 pub extern "system" fn DllCanUnloadNow() -> HRESULT {
@@ -75,7 +187,16 @@ pub extern "system" fn DllGetClassObject(
     riid: *const GUID,
     ppv: *mut *mut std::ffi::c_void,
 ) -> HRESULT {
-    HRESULT(1)
+    if rclsid.is_null() || riid.is_null() || ppv.is_null() {
+        return E_FAIL;
+    }
+    unsafe {
+        if *rclsid != CLSID_COMIC_THUMB_PROVIDER {
+            return CLASS_E_CLASSNOTAVAILABLE;
+        }
+        let factory: IUnknown = ComicThumbProviderFactory.into();
+        factory.query(&*riid, ppv)
+    }
 }
 
 #[unsafe(no_mangle)]
@@ -128,4 +249,4 @@ pub extern "system" fn DllUnregisterServer() -> i32 {
     }
 
     0 // S_OK
-}
\ No newline at end of file
+}
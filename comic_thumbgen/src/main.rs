@@ -1,4 +1,6 @@
-use comic_archive::{ImageArchive, error::ArchiveError};
+use comic_archive::{
+    DEFAULT_THUMBNAIL_QUALITY, DEFAULT_THUMBNAIL_SIZE, ImageArchive, error::ArchiveError,
+};
 use std::env;
 use std::fs::File;
 use std::io::Write;
@@ -46,7 +48,11 @@ fn main() {
         None => &image_list[0],
     };
 
-    let thumb = match archive.generate_thumbnail(image_to_use) {
+    let thumb = match archive.generate_thumbnail(
+        image_to_use,
+        DEFAULT_THUMBNAIL_SIZE,
+        DEFAULT_THUMBNAIL_QUALITY,
+    ) {
         Ok(buf) => buf,
         Err(e) => {
             eprintln!("Failed to generate thumbnail: {e}");
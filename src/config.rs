@@ -1,7 +0,0 @@
-pub const WIN_WIDTH: f32 = 720.0;
-pub const WIN_HEIGHT: f32 = 1080.0;
-pub const CACHE_SIZE: usize = 20;
-pub const PAGE_MARGIN_SIZE: usize = 0;
-pub const DEFAULT_DUAL_PAGE_MODE: bool = false;
-pub const DEFAULT_RIGHT_TO_LEFT: bool = false;
-pub const READING_DIRECTION_AFFECTS_ARROWS: bool = true;
\ No newline at end of file
@@ -1,8 +0,0 @@
-//! UI rendering and layout.
-
-pub mod layout;
-pub mod image;
-pub mod log;
-pub use layout::*;
-pub use image::*;
-pub use log::*;
\ No newline at end of file